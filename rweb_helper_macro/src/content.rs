@@ -0,0 +1,49 @@
+/// Check a media type against the RFC 6838 `type "/" subtype *(";" parameter)`
+/// grammar.
+pub(crate) fn validate_mime(mime: &str) -> Result<(), String> {
+    let mut parts = mime.split(';');
+    let essence = parts.next().unwrap_or_default().trim();
+    let (type_, subtype) = essence
+        .split_once('/')
+        .ok_or_else(|| format!("{} is not a valid MIME type, expected type/subtype", mime))?;
+    for name in &[type_, subtype] {
+        if !is_restricted_name(name) {
+            return Err(format!(
+                "{} is not a valid MIME type: bad name {:?}",
+                mime, name
+            ));
+        }
+    }
+    for param in parts {
+        let param = param.trim();
+        match param.split_once('=') {
+            Some((key, value)) if is_restricted_name(key.trim()) && !value.trim().is_empty() => {}
+            _ => {
+                return Err(format!(
+                    "{} is not a valid MIME type: bad parameter {:?}",
+                    mime, param
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_restricted_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphanumeric() => {}
+        _ => return false,
+    }
+    name.len() <= 127 && chars.all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+}
+
+/// The `Content-Type` header for a media type; text types get an explicit
+/// utf-8 charset, like `derive_content_type!`.
+pub(crate) fn content_type_header(mime: &str) -> String {
+    if mime.starts_with("text/") && !mime.contains("charset") {
+        format!("{}; charset=utf-8", mime)
+    } else {
+        mime.to_string()
+    }
+}
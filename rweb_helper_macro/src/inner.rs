@@ -0,0 +1,99 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Field, Fields, Member, Type};
+
+/// The wrapped response of a `RwebResponse` struct: either the single field
+/// of a tuple struct, or the named field marked `#[response(inner)]` (which
+/// may be omitted when it is the only field).
+///
+/// The generated `From<inner>` fills the other fields of a named struct with
+/// `Default::default()`, so each of them must implement `Default`; a field
+/// that doesn't is reported at its own type.
+pub(crate) struct InnerField {
+    pub(crate) ty: Type,
+    pub(crate) access: Member,
+    pub(crate) construct: TokenStream,
+}
+
+impl InnerField {
+    pub(crate) fn find(fields: &Fields) -> syn::Result<Self> {
+        match fields {
+            Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+                let field = &unnamed.unnamed[0];
+                Ok(Self {
+                    ty: field.ty.clone(),
+                    access: Member::Unnamed(0.into()),
+                    construct: quote! {Self(item)},
+                })
+            }
+            Fields::Named(named) => {
+                let mut marked = Vec::new();
+                for field in &named.named {
+                    if is_marked_inner(field)? {
+                        marked.push(field);
+                    }
+                }
+                let inner = match (marked.len(), named.named.len()) {
+                    (1, _) => marked[0],
+                    (0, 1) => &named.named[0],
+                    (0, _) => {
+                        return Err(syn::Error::new(
+                            named.span(),
+                            "mark the wrapped response field with #[response(inner)]",
+                        ))
+                    }
+                    _ => {
+                        return Err(syn::Error::new(
+                            marked[1].span(),
+                            "only one field can be marked #[response(inner)]",
+                        ))
+                    }
+                };
+                let inner_ident = inner.ident.clone().expect("named field");
+                let others = named
+                    .named
+                    .iter()
+                    .filter(|f| f.ident.as_ref() != Some(&inner_ident))
+                    .map(|f| {
+                        let ident = &f.ident;
+                        let ty = &f.ty;
+                        quote_spanned! {ty.span()=>
+                            #ident: <#ty as ::core::default::Default>::default()
+                        }
+                    });
+                Ok(Self {
+                    ty: inner.ty.clone(),
+                    construct: quote! {
+                        Self {
+                            #inner_ident: item,
+                            #(#others,)*
+                        }
+                    },
+                    access: Member::Named(inner_ident),
+                })
+            }
+            _ => Err(syn::Error::new(
+                fields.span(),
+                "RwebResponse requires a single field tuple struct or a named field marked \
+                 #[response(inner)]",
+            )),
+        }
+    }
+}
+
+fn is_marked_inner(field: &Field) -> syn::Result<bool> {
+    let mut inner = false;
+    for attr in &field.attrs {
+        if attr.path().is_ident("response") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("inner") {
+                    inner = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `inner`"))
+                }
+            })?;
+        }
+    }
+    Ok(inner)
+}
@@ -1,98 +1,217 @@
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{
-    spanned::Spanned, Data, DeriveInput, Expr, Fields, Lit, Meta, PathArguments, Token, Type,
-    TypePath,
-};
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Expr, Lit, LitStr, Meta, Type};
+
+mod content;
+mod deprecation;
+mod inner;
+mod schema_as;
+mod serde_attr;
+mod status;
+mod validate;
+
+use content::{content_type_header, validate_mime};
+use deprecation::sunset_http_date;
+use inner::InnerField;
+use status::parse_status;
 
 #[proc_macro_derive(RwebResponse, attributes(response))]
 pub fn derive_rweb_response_fn(input: TokenStream) -> TokenStream {
+    #[derive(Default, Debug)]
+    struct ResponseHeader {
+        name: Option<LitStr>,
+        schema: Option<LitStr>,
+        description: Option<String>,
+    }
+    #[derive(Debug)]
+    enum ResponseExample {
+        Function(syn::Path),
+        Json(LitStr),
+    }
     #[derive(Default, Debug)]
     struct RwebResponse {
         description: Option<String>,
-        content: Option<String>,
-        status: Option<String>,
+        content: Option<LitStr>,
+        content_type: Option<LitStr>,
+        status: Option<LitStr>,
         error: Option<String>,
+        headers: Vec<ResponseHeader>,
+        example: Option<ResponseExample>,
+        deprecated: bool,
+        sunset: Option<LitStr>,
+        link: Option<LitStr>,
     }
     let mut rweb_response = RwebResponse::default();
     let input: DeriveInput = syn::parse(input).expect("Failed to parse");
     let DeriveInput {
-        attrs, ident, data, ..
+        attrs,
+        ident,
+        generics,
+        data,
+        ..
     } = input;
     for attr in &attrs {
         if attr.meta.path().is_ident("response") {
             if let Meta::List(metalist) = &attr.meta {
-                metalist
-                    .parse_nested_meta(|meta| {
-                        if let Some(ident) = meta.path.get_ident() {
-                            let ident = ident.to_string();
-                            if let Expr::Lit(lit) = meta.value()?.parse::<Expr>()? {
-                                if let Lit::Str(lit) = lit.lit {
-                                    let lit = Some(lit.value());
-                                    match ident.as_str() {
-                                        "description" => rweb_response.description = lit,
-                                        "content" => rweb_response.content = lit,
-                                        "status" => rweb_response.status = lit,
-                                        "error" => rweb_response.error = lit,
-                                        id => panic!("{} is not a valid key", id),
-                                    }
+                let parsed = metalist.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("deprecated") {
+                        rweb_response.deprecated = true;
+                    } else if meta.path.is_ident("header") {
+                        let mut header = ResponseHeader::default();
+                        meta.parse_nested_meta(|meta| {
+                            let lit: LitStr = meta.value()?.parse()?;
+                            if meta.path.is_ident("name") {
+                                header.name = Some(lit);
+                            } else if meta.path.is_ident("schema") {
+                                header.schema = Some(lit);
+                            } else if meta.path.is_ident("description") {
+                                header.description = Some(lit.value());
+                            } else {
+                                return Err(meta.error("expected name, schema or description"));
+                            }
+                            Ok(())
+                        })?;
+                        rweb_response.headers.push(header);
+                    } else if meta.path.is_ident("example") {
+                        let example = match meta.value()?.parse::<Expr>()? {
+                            Expr::Path(path) => ResponseExample::Function(path.path),
+                            Expr::Lit(syn::ExprLit {
+                                lit: Lit::Str(lit), ..
+                            }) => {
+                                if let Err(e) =
+                                    serde_json::from_str::<serde_json::Value>(&lit.value())
+                                {
+                                    return Err(syn::Error::new(
+                                        lit.span(),
+                                        format!("example is not valid json: {}", e),
+                                    ));
+                                }
+                                ResponseExample::Json(lit)
+                            }
+                            _ => {
+                                return Err(
+                                    meta.error("example must be a function path or a json string")
+                                )
+                            }
+                        };
+                        rweb_response.example = Some(example);
+                    } else if let Some(ident) = meta.path.get_ident() {
+                        let ident = ident.to_string();
+                        if let Expr::Lit(lit) = meta.value()?.parse::<Expr>()? {
+                            if let Lit::Str(lit) = lit.lit {
+                                let value = Some(lit.value());
+                                match ident.as_str() {
+                                    "description" => rweb_response.description = value,
+                                    "content" => rweb_response.content = Some(lit),
+                                    "content_type" => rweb_response.content_type = Some(lit),
+                                    "status" => rweb_response.status = Some(lit),
+                                    "error" => rweb_response.error = value,
+                                    "sunset" => rweb_response.sunset = Some(lit),
+                                    "link" => rweb_response.link = Some(lit),
+                                    id => panic!("{} is not a valid key", id),
                                 }
                             }
                         }
-                        Ok(())
-                    })
-                    .map_err(|e| panic!("encountered error {}", e))
-                    .unwrap();
-            }
-        }
-    }
-    let mut inner_type: Option<TypePath> = None;
-    if let Data::Struct(data_struct) = data {
-        if let Fields::Unnamed(fields) = data_struct.fields {
-            if let Some(first) = fields.unnamed.first() {
-                if let Type::Path(typath) = &first.ty {
-                    inner_type = Some(typath.clone());
+                    }
+                    Ok(())
+                });
+                if let Err(e) = parsed {
+                    return e.to_compile_error().into();
                 }
             }
         }
     }
-    let inner_type = inner_type.expect("No inner type");
-    let mut inner_type_mod = inner_type.clone();
-    if let Some(first) = inner_type_mod.path.segments.first_mut() {
-        if let PathArguments::AngleBracketed(args) = &mut first.arguments {
-            args.colon2_token = Some(Token![::](args.span()));
+    let inner = match &data {
+        Data::Struct(data_struct) => InnerField::find(&data_struct.fields),
+        _ => Err(syn::Error::new(
+            ident.span(),
+            "RwebResponse can only be derived for structs",
+        )),
+    };
+    let inner = match inner {
+        Ok(inner) => inner,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let inner_type = &inner.ty;
+    let inner_access = &inner.access;
+    let construct = &inner.construct;
+    let where_clause_for = |bound: proc_macro2::TokenStream| {
+        let mut generics = generics.clone();
+        if !generics.params.is_empty() {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote!(#inner_type: #bound));
         }
-    }
+        generics.where_clause
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let reply_where = where_clause_for(quote! {rweb::Reply});
+    let entity_where = where_clause_for(quote! {rweb::openapi::Entity});
+    let response_entity_where = where_clause_for(quote! {rweb::openapi::ResponseEntity});
+    let example_where = where_clause_for(quote! {rweb_helper::example::ResponseExample});
     let from_impl = quote! {
-        impl From<#inner_type> for #ident {
+        impl #impl_generics From<#inner_type> for #ident #ty_generics #where_clause {
             fn from(item: #inner_type) -> Self {
-                Self(item)
+                #construct
             }
         }
     };
-    let content = match rweb_response.content.as_deref() {
-        Some("html") => Some(quote! {rweb_helper::content_type_trait::ContentTypeHtml}),
-        Some("css") => Some(quote! {rweb_helper::content_type_trait::ContentTypeCss}),
-        Some("js") => Some(quote! {rweb_helper::content_type_trait::ContentTypeJs}),
-        Some(val) => panic!("{} is not a valid content type", val),
+    let content_trait = |ty| {
+        let content_trait = quote! {<#ty as rweb_helper::content_type_trait::ContentTypeTrait>};
+        (
+            quote! {#content_trait::content_type()},
+            quote! {#content_trait::content_type_header()},
+        )
+    };
+    let content = match (&rweb_response.content, &rweb_response.content_type) {
+        (Some(_), Some(lit)) => {
+            return syn::Error::new(
+                lit.span(),
+                "content and content_type are mutually exclusive",
+            )
+            .to_compile_error()
+            .into()
+        }
+        (Some(lit), None) => match lit.value().as_str() {
+            "html" => Some(content_trait(
+                quote! {rweb_helper::content_type_trait::ContentTypeHtml},
+            )),
+            "css" => Some(content_trait(
+                quote! {rweb_helper::content_type_trait::ContentTypeCss},
+            )),
+            "js" => Some(content_trait(
+                quote! {rweb_helper::content_type_trait::ContentTypeJs},
+            )),
+            mime => {
+                if let Err(e) = validate_mime(mime) {
+                    return syn::Error::new(lit.span(), e).to_compile_error().into();
+                }
+                let header = content_type_header(mime);
+                Some((quote! {#mime}, quote! {#header}))
+            }
+        },
+        (None, Some(lit)) => match lit.parse::<syn::Path>() {
+            Ok(path) => Some(content_trait(quote! {#path})),
+            Err(e) => return e.to_compile_error().into(),
+        },
+        (None, None) => None,
+    };
+    let status = match &rweb_response.status {
+        Some(lit) => match parse_status(&lit.value()) {
+            Ok(200) => Some(quote! {rweb_helper::status_code_trait::StatusCodeOk}),
+            Ok(201) => Some(quote! {rweb_helper::status_code_trait::StatusCodeCreated}),
+            Ok(204) => Some(quote!(rweb_helper::status_code_trait::StatusCodeNoContent)),
+            Ok(c) => Some(quote!(rweb_helper::status_code_trait::StatusCodeValue::<#c>)),
+            Err(e) => return syn::Error::new(lit.span(), e).to_compile_error().into(),
+        },
         None => None,
     };
-    let status = match rweb_response.status.as_deref() {
-        Some("OK") => Some(quote! {rweb_helper::status_code_trait::StatusCodeOk}),
-        Some("CREATED") => Some(quote! {rweb_helper::status_code_trait::StatusCodeCreated}),
-        Some("NO_CONTENT") => Some(quote!(rweb_helper::status_code_trait::StatusCodeNoContent)),
-        Some(s) => s
-            .parse::<u16>()
-            .ok()
-            .map(|c| quote!(rweb_helper::status_code_trait::StatusCodeValue::<#c>)),
-        _ => None,
-    };
-    let content_reply = if let Some(content) = &content {
+    let content_reply = if let Some((_, content_header)) = &content {
         quote! {
-            use rweb_helper::content_type_trait::ContentTypeTrait;
             res.headers_mut().insert(
                 rweb::http::header::CONTENT_TYPE ,
-                rweb::http::HeaderValue::from_static( #content::content_type_header() )
+                rweb::http::HeaderValue::from_static( #content_header )
             );
         }
     } else {
@@ -106,35 +225,160 @@ pub fn derive_rweb_response_fn(input: TokenStream) -> TokenStream {
     } else {
         quote! {}
     };
+    let sunset = match &rweb_response.sunset {
+        Some(lit) => match sunset_http_date(&lit.value()) {
+            Ok(date) => Some((lit.value(), date)),
+            Err(e) => return syn::Error::new(lit.span(), e).to_compile_error().into(),
+        },
+        None => None,
+    };
+    if let Some(link) = &rweb_response.link {
+        let value = link.value();
+        if value.is_empty()
+            || !value
+                .bytes()
+                .all(|b| b.is_ascii_graphic() && b != b'<' && b != b'>')
+        {
+            return syn::Error::new(link.span(), "not a valid link url")
+                .to_compile_error()
+                .into();
+        }
+    }
+    let deprecated = rweb_response.deprecated || sunset.is_some() || rweb_response.link.is_some();
+    let header_decl = |name: &str, description: &str| ResponseHeader {
+        name: Some(LitStr::new(name, proc_macro2::Span::call_site())),
+        schema: None,
+        description: Some(description.into()),
+    };
+    let mut deprecation_reply = Vec::new();
+    let mut deprecation_note = Vec::new();
+    if deprecated {
+        rweb_response.headers.push(header_decl(
+            "Deprecation",
+            "Set when the endpoint is deprecated",
+        ));
+        deprecation_reply.push(quote! {
+            res.headers_mut().insert(
+                rweb::http::header::HeaderName::from_static("deprecation"),
+                rweb::http::HeaderValue::from_static("true"),
+            );
+        });
+    }
+    if let Some((date, http_date)) = &sunset {
+        deprecation_note.push(format!("Will be removed on {}.", date));
+        rweb_response.headers.push(header_decl(
+            "Sunset",
+            "Date after which the endpoint will be removed (RFC 8594)",
+        ));
+        deprecation_reply.push(quote! {
+            res.headers_mut().insert(
+                rweb::http::header::HeaderName::from_static("sunset"),
+                rweb::http::HeaderValue::from_static(#http_date),
+            );
+        });
+    }
+    if let Some(link) = &rweb_response.link {
+        let link = link.value();
+        deprecation_note.push(format!("See {}.", link));
+        rweb_response.headers.push(header_decl(
+            "Link",
+            "Link to the deprecation notice, with rel=\"deprecation\"",
+        ));
+        let link_value = format!("<{}>; rel=\"deprecation\"", link);
+        deprecation_reply.push(quote! {
+            res.headers_mut().append(
+                rweb::http::header::LINK,
+                rweb::http::HeaderValue::from_static(#link_value),
+            );
+        });
+    }
+    let mut header_names = Vec::new();
+    let mut header_docs = Vec::new();
+    for header in &rweb_response.headers {
+        let name = match &header.name {
+            Some(name) => name,
+            None => {
+                return syn::Error::new(ident.span(), "header requires a name")
+                    .to_compile_error()
+                    .into()
+            }
+        };
+        let name_value = name.value();
+        if name_value.is_empty()
+            || !name_value
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+        {
+            return syn::Error::new(name.span(), "not a valid header name")
+                .to_compile_error()
+                .into();
+        }
+        let schema = match &header.schema {
+            Some(schema) => match schema.parse::<Type>() {
+                Ok(ty) => quote! {#ty},
+                Err(e) => return e.to_compile_error().into(),
+            },
+            None => quote! {String},
+        };
+        let description = match &header.description {
+            Some(description) => quote! {Some(#description.into())},
+            None => quote! {None},
+        };
+        header_docs.push(quote! {
+            old.headers.insert(
+                #name_value.into(),
+                rweb::openapi::ObjectOrReference::Object(rweb::openapi::Header {
+                    description: #description,
+                    schema: Some(<#schema as rweb::openapi::Entity>::describe(comp_d)),
+                    ..rweb::openapi::Header::default()
+                }),
+            );
+        });
+        header_names.push(name_value.to_lowercase());
+    }
+    let header_reply = if header_names.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            for name in &[#(#header_names),*] {
+                debug_assert!(
+                    res.headers().contains_key(*name),
+                    "{} response is missing declared header {}",
+                    stringify!(#ident),
+                    name
+                );
+            }
+        }
+    };
     let reply_impl = quote! {
-        impl rweb::Reply for #ident {
+        impl #impl_generics rweb::Reply for #ident #ty_generics #reply_where {
             fn into_response(self) -> rweb::http::Response<rweb::hyper::Body> {
-                let mut res = self.0.into_response();
+                let mut res = rweb::Reply::into_response(self.#inner_access);
                 #content_reply
                 #status_reply
+                #(#deprecation_reply)*
+                #header_reply
                 res
             }
         }
     };
     let entity_impl = quote! {
-        impl rweb::openapi::Entity for #ident {
+        impl #impl_generics rweb::openapi::Entity for #ident #ty_generics #entity_where {
             fn type_name() -> std::borrow::Cow<'static, str> {
-                #inner_type_mod::type_name()
+                <#inner_type as rweb::openapi::Entity>::type_name()
             }
             fn describe(comp_d: &mut rweb::openapi::ComponentDescriptor) -> rweb::openapi::ComponentOrInlineSchema {
-                #inner_type_mod::describe(comp_d)
+                <#inner_type as rweb::openapi::Entity>::describe(comp_d)
             }
         }
     };
-    let content_response_entity = if let Some(content) = &content {
+    let content_response_entity = if let Some((content_type, _)) = &content {
         quote! {
-            let old_code: std::borrow::Cow<'static, str> = "200".into();
-            if let Some(mut old) = resp.get_mut(&old_code) {
-                use rweb_helper::content_type_trait::ContentTypeTrait;
-                let old_content_type: std::borrow::Cow<'static, str> = "text/plain".into();
-                let new_content_type: std::borrow::Cow<'static, str> = #content::content_type().into();
-                if let Some(old_content) = old.content.remove(&old_content_type) {
-                    old.content.insert(new_content_type, old_content);
+            if let Some(mut old) = resp.get_mut(&code) {
+                let new_content_type: std::borrow::Cow<'static, str> = #content_type.into();
+                let old_content = std::mem::take(&mut old.content);
+                if let Some((_, media)) = old_content.into_iter().next() {
+                    old.content.insert(new_content_type, media);
                 }
             }
         }
@@ -143,33 +387,117 @@ pub fn derive_rweb_response_fn(input: TokenStream) -> TokenStream {
     };
     let description_response_entity = if let Some(description) = &rweb_response.description {
         quote! {
-            let old_code: std::borrow::Cow<'static, str> = "200".into();
-            if let Some(mut old) = resp.get_mut(&old_code) {
+            if let Some(mut old) = resp.get_mut(&code) {
                 old.description = #description.into();
             }
         }
     } else {
         quote! {}
     };
+    let deprecation_response_entity = if deprecated {
+        let deprecation_note = deprecation_note.join(" ");
+        quote! {
+            if let Some(old) = resp.get_mut(&code) {
+                rweb_helper::response_description_trait::deprecate_response(old, #deprecation_note);
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let (example_response_entity, example_impl) = match &rweb_response.example {
+        Some(example) => {
+            let value = match example {
+                ResponseExample::Function(path) => quote! {
+                    <#inner_type as rweb_helper::example::ResponseExample>::example_value(&#path())
+                },
+                ResponseExample::Json(lit) => quote! {
+                    rweb_helper::example::parse_example(#lit)
+                },
+            };
+            // The schema is only known at runtime, so the example is checked
+            // by a generated test; non-generic types only, since the test
+            // needs a concrete type.
+            let example_test = if generics.params.is_empty() {
+                let test_ident = format_ident!("__rweb_response_example_{}", ident);
+                quote! {
+                    #[cfg(test)]
+                    #[test]
+                    #[allow(non_snake_case)]
+                    fn #test_ident() {
+                        if let Err(e) = #ident::check_response_example() {
+                            panic!("example of {} does not match its schema: {}", stringify!(#ident), e);
+                        }
+                    }
+                }
+            } else {
+                quote! {}
+            };
+            let example_impl = quote! {
+                impl #impl_generics #ident #ty_generics #example_where {
+                    /// Check the documented example against the schema of the
+                    /// response body.
+                    #[doc(hidden)]
+                    pub fn check_response_example() -> Result<(), String> {
+                        let example = #value?;
+                        <#inner_type as rweb_helper::example::ResponseExample>::check_example(&example)
+                    }
+                }
+                #example_test
+            };
+            let example_response_entity = quote! {
+                if let Some(old) = resp.get_mut(&code) {
+                    let example = #value.and_then(|example| {
+                        <#inner_type as rweb_helper::example::ResponseExample>::check_example(&example)
+                            .map(|()| example)
+                    });
+                    // A mismatching example is left out, the generated test
+                    // reports it.
+                    if let Ok(example) = example {
+                        rweb_helper::example::document_example(old, &example);
+                    }
+                }
+            };
+            (example_response_entity, example_impl)
+        }
+        None => (quote! {}, quote! {}),
+    };
+    let header_response_entity = if header_docs.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            if let Some(old) = resp.get_mut(&code) {
+                #(#header_docs)*
+            }
+        }
+    };
     let status_response_entity = if let Some(status) = &status {
         quote! {
             use rweb_helper::status_code_trait::StatusCodeTrait;
-            let old_code: std::borrow::Cow<'static, str> = "200".into();
             let new_code: std::borrow::Cow<'static, str> = #status::status_code().as_u16().to_string().into();
-            if let Some(old) = resp.remove(&old_code) {
-                resp.insert(new_code, old);
+            if let Some(old) = resp.remove(&code) {
+                resp.insert(new_code.clone(), old);
             }
+            let code = new_code;
         }
     } else {
         quote! {}
     };
     let response_entity_impl = quote! {
-        impl rweb::openapi::ResponseEntity for #ident {
+        impl #impl_generics rweb::openapi::ResponseEntity for #ident #ty_generics #response_entity_where {
             fn describe_responses(comp_d: &mut rweb::openapi::ComponentDescriptor) -> rweb::openapi::Responses {
-                let mut resp = #inner_type_mod::describe_responses(comp_d);
+                let mut resp = <#inner_type as rweb::openapi::ResponseEntity>::describe_responses(comp_d);
+                #[allow(unused_variables)]
+                let code: std::borrow::Cow<'static, str> = resp
+                    .keys()
+                    .find(|code| code.starts_with('2'))
+                    .cloned()
+                    .unwrap_or_else(|| "200".into());
+                #status_response_entity
                 #content_response_entity
                 #description_response_entity
-                #status_response_entity
+                #deprecation_response_entity
+                #example_response_entity
+                #header_response_entity
                 resp.sort_keys();
                 resp
             }
@@ -180,7 +508,25 @@ pub fn derive_rweb_response_fn(input: TokenStream) -> TokenStream {
         #reply_impl
         #entity_impl
         #response_entity_impl
+        #example_impl
     };
     tokens.into()
 }
 
+#[proc_macro_derive(RwebSchemaAs, attributes(rweb_schema_as))]
+pub fn derive_rweb_schema_as_fn(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = syn::parse(input).expect("Failed to parse");
+    match schema_as::derive(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(Validate, attributes(validate))]
+pub fn derive_validate_fn(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = syn::parse(input).expect("Failed to parse");
+    match validate::derive(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
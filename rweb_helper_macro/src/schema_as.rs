@@ -0,0 +1,172 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{spanned::Spanned, Attribute, Data, DeriveInput, Fields, Ident, LitStr, Type};
+
+use crate::serde_attr::{rename_all, serde_field};
+
+fn target(attrs: &[Attribute], span: Span) -> syn::Result<Type> {
+    attrs
+        .iter()
+        .find(|a| a.path().is_ident("rweb_schema_as"))
+        .ok_or_else(|| syn::Error::new(span, "missing #[rweb_schema_as(TargetType)]"))?
+        .parse_args()
+}
+
+/// The field of the target mirrored by a source field: the same rust name
+/// unless set with `#[rweb_schema_as(field = "name")]`.
+fn target_field(attrs: &[Attribute], ident: &Ident) -> syn::Result<Ident> {
+    let mut target = ident.clone();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("rweb_schema_as")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("field") {
+                let lit: LitStr = meta.value()?.parse()?;
+                target = lit.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("expected field"))
+            }
+        })?;
+    }
+    Ok(target)
+}
+
+pub(crate) fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let DeriveInput {
+        attrs,
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+    if !generics.params.is_empty() {
+        return Err(syn::Error::new(
+            generics.span(),
+            "RwebSchemaAs does not support generic structs",
+        ));
+    }
+    let target = target(&attrs, ident.span())?;
+    let fields = match data {
+        Data::Struct(data) => data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                ident.span(),
+                "RwebSchemaAs can only be derived for structs",
+            ))
+        }
+    };
+    let check = match &fields {
+        Fields::Named(named) => {
+            let rename_all = rename_all(&attrs)?;
+            let mut source_fields = Vec::new();
+            let mut field_checks = Vec::new();
+            for field in &named.named {
+                let serde = serde_field(&field.attrs)?;
+                if serde.skip {
+                    continue;
+                }
+                let ty = &field.ty;
+                let schema = quote_spanned! {ty.span()=>
+                    <#ty as rweb::openapi::Entity>::describe(comp_d)
+                };
+                if serde.flatten {
+                    source_fields.push(quote! {
+                        rweb_helper::schema_as::SourceField::Flattened(#schema)
+                    });
+                } else {
+                    let field_ident = field.ident.as_ref().expect("named field");
+                    let name = serde.name(field_ident, rename_all.as_ref())?;
+                    source_fields.push(quote! {
+                        rweb_helper::schema_as::SourceField::Named(#name, #schema)
+                    });
+                    let target_field = target_field(&field.attrs, field_ident)?;
+                    field_checks.push(quote_spanned! {ty.span()=>
+                        __SchemaAsField::of(&target.#target_field).check::<#ty>();
+                    });
+                }
+            }
+            // The rust types of the fields are checked at compile time.  The
+            // serialized names can only be compared with the properties of
+            // the target schema, which reflect its own serde renames and
+            // skips, so that is left to a generated test.
+            let test_ident = format_ident!("__rweb_schema_as_{}", ident);
+            quote! {
+                #[allow(dead_code, unused_variables, clippy::all)]
+                const _: () = {
+                    struct __SchemaAsField<T: ?Sized>(std::marker::PhantomData<T>);
+                    impl<T: ?Sized> __SchemaAsField<T> {
+                        fn of(_: &T) -> Self {
+                            Self(std::marker::PhantomData)
+                        }
+                        fn check<S: rweb_helper::SchemaCompatible<T> + ?Sized>(&self) {}
+                    }
+                    fn __schema_as_fields(target: &#target) {
+                        #(#field_checks)*
+                    }
+                };
+
+                impl #ident {
+                    /// Check the serialized fields against the schema of the
+                    /// target.
+                    #[doc(hidden)]
+                    pub fn check_schema_as() -> Result<(), String> {
+                        let comp_d = &mut rweb::openapi::ComponentDescriptor::new();
+                        let target = <#target as rweb::openapi::Entity>::describe(comp_d);
+                        let fields = vec![#(#source_fields),*];
+                        rweb_helper::schema_as::check_fields(comp_d, &target, fields)
+                    }
+                }
+
+                #[cfg(test)]
+                #[test]
+                #[allow(non_snake_case)]
+                fn #test_ident() {
+                    if let Err(e) = #ident::check_schema_as() {
+                        panic!(
+                            "{} does not match the schema of {}: {}",
+                            stringify!(#ident),
+                            stringify!(#target),
+                            e
+                        );
+                    }
+                }
+            }
+        }
+        Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+            let inner = &unnamed.unnamed[0].ty;
+            quote_spanned! {inner.span()=>
+                #[allow(dead_code, clippy::all)]
+                const _: fn() = || {
+                    fn __schema_as_compatible<S, T>()
+                    where
+                        S: rweb_helper::SchemaCompatible<T> + ?Sized,
+                        T: ?Sized,
+                    {
+                    }
+                    __schema_as_compatible::<#inner, #target>();
+                };
+            }
+        }
+        _ => {
+            return Err(syn::Error::new(
+                fields.span(),
+                "RwebSchemaAs requires named fields or a single field tuple struct",
+            ))
+        }
+    };
+    Ok(quote! {
+        #check
+
+        impl rweb_helper::SchemaCompatible<#target> for #ident {}
+
+        impl rweb::openapi::Entity for #ident {
+            fn type_name() -> std::borrow::Cow<'static, str> {
+                <#target as rweb::openapi::Entity>::type_name()
+            }
+            fn describe(
+                comp_d: &mut rweb::openapi::ComponentDescriptor,
+            ) -> rweb::openapi::ComponentOrInlineSchema {
+                <#target as rweb::openapi::Entity>::describe(comp_d)
+            }
+        }
+    })
+}
@@ -0,0 +1,127 @@
+use proc_macro2::TokenStream;
+use syn::{meta::ParseNestedMeta, token, Attribute, Ident, LitStr};
+
+/// How serde names a struct field, as far as it matters for the schema.
+pub(crate) struct SerdeField {
+    pub(crate) skip: bool,
+    pub(crate) flatten: bool,
+    pub(crate) rename: Option<String>,
+}
+
+impl SerdeField {
+    /// Name of the field in the serialized output; flattened fields keep
+    /// their rust name.
+    pub(crate) fn name(&self, ident: &Ident, rename_all: Option<&LitStr>) -> syn::Result<String> {
+        match (&self.rename, rename_all, self.flatten) {
+            (Some(rename), _, false) => Ok(rename.clone()),
+            (None, Some(rule), false) => apply_rename_all(rule, &ident.to_string()),
+            _ => Ok(ident.to_string()),
+        }
+    }
+}
+
+pub(crate) fn serde_field(attrs: &[Attribute]) -> syn::Result<SerdeField> {
+    let mut field = SerdeField {
+        skip: false,
+        flatten: false,
+        rename: None,
+    };
+    for attr in attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") || meta.path.is_ident("skip_serializing") {
+                field.skip = true;
+            } else if meta.path.is_ident("flatten") {
+                field.flatten = true;
+            } else if meta.path.is_ident("rename") {
+                if let Ok(value) = meta.value() {
+                    let lit: LitStr = value.parse()?;
+                    field.rename = Some(lit.value());
+                } else {
+                    meta.parse_nested_meta(|meta| {
+                        let lit: LitStr = meta.value()?.parse()?;
+                        if meta.path.is_ident("serialize") {
+                            field.rename = Some(lit.value());
+                        }
+                        Ok(())
+                    })?;
+                }
+            } else {
+                // other serde keys (default, with, bound(...), ...) don't
+                // change the field name
+                skip_meta(&meta)?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(field)
+}
+
+pub(crate) fn rename_all(attrs: &[Attribute]) -> syn::Result<Option<LitStr>> {
+    let mut rename_all = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename_all") {
+                if let Ok(value) = meta.value() {
+                    rename_all = Some(value.parse()?);
+                } else {
+                    meta.parse_nested_meta(|meta| {
+                        let lit: LitStr = meta.value()?.parse()?;
+                        if meta.path.is_ident("serialize") {
+                            rename_all = Some(lit);
+                        }
+                        Ok(())
+                    })?;
+                }
+            } else {
+                skip_meta(&meta)?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(rename_all)
+}
+
+/// Consume the value of a serde key we don't look at, whether it is
+/// `key`, `key = value` or `key(...)`.
+fn skip_meta(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(token::Paren) {
+        let content;
+        syn::parenthesized!(content in meta.input);
+        content.parse::<TokenStream>()?;
+    } else if let Ok(value) = meta.value() {
+        value.parse::<syn::Expr>()?;
+    }
+    Ok(())
+}
+
+fn apply_rename_all(rule: &LitStr, name: &str) -> syn::Result<String> {
+    let words: Vec<&str> = name.split('_').filter(|w| !w.is_empty()).collect();
+    let capitalize = |w: &str| {
+        let mut chars = w.chars();
+        chars
+            .next()
+            .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+            .unwrap_or_default()
+    };
+    let renamed = match rule.value().as_str() {
+        "lowercase" => name.to_lowercase(),
+        "UPPERCASE" => name.to_uppercase(),
+        "snake_case" => name.into(),
+        "SCREAMING_SNAKE_CASE" => name.to_uppercase(),
+        "PascalCase" => words.iter().map(|w| capitalize(w)).collect(),
+        "camelCase" => words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| if i == 0 { (*w).into() } else { capitalize(w) })
+            .collect(),
+        "kebab-case" => name.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => name.to_uppercase().replace('_', "-"),
+        other => {
+            return Err(syn::Error::new(
+                rule.span(),
+                format!("unknown rename_all rule \"{}\"", other),
+            ))
+        }
+    };
+    Ok(renamed)
+}
@@ -0,0 +1,268 @@
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{
+    spanned::Spanned, Data, DeriveInput, Expr, ExprLit, ExprUnary, Fields, Lit, LitInt, LitStr,
+    Path, UnOp,
+};
+
+use crate::serde_attr::{rename_all, serde_field};
+
+#[derive(Default)]
+struct FieldRules {
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    pattern: Option<LitStr>,
+    email: bool,
+    url: bool,
+    custom: Vec<Path>,
+}
+
+impl FieldRules {
+    fn is_empty(&self) -> bool {
+        self.min_length.is_none()
+            && self.max_length.is_none()
+            && self.minimum.is_none()
+            && self.maximum.is_none()
+            && self.pattern.is_none()
+            && !self.email
+            && !self.url
+            && self.custom.is_empty()
+    }
+}
+
+fn parse_number(expr: &Expr) -> syn::Result<f64> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse(),
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(lit),
+            ..
+        }) => lit.base10_parse(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => parse_number(expr).map(|n| -n),
+        _ => Err(syn::Error::new(expr.span(), "expected a number literal")),
+    }
+}
+
+fn field_rules(attrs: &[syn::Attribute], span: Span) -> syn::Result<FieldRules> {
+    let mut rules = FieldRules::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("validate")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("length") {
+                meta.parse_nested_meta(|meta| {
+                    let value: LitInt = meta.value()?.parse()?;
+                    if meta.path.is_ident("min") {
+                        rules.min_length = Some(value.base10_parse()?);
+                    } else if meta.path.is_ident("max") {
+                        rules.max_length = Some(value.base10_parse()?);
+                    } else if meta.path.is_ident("equal") {
+                        rules.min_length = Some(value.base10_parse()?);
+                        rules.max_length = rules.min_length;
+                    } else {
+                        return Err(meta.error("expected min, max or equal"));
+                    }
+                    Ok(())
+                })
+            } else if meta.path.is_ident("range") {
+                meta.parse_nested_meta(|meta| {
+                    let value = parse_number(&meta.value()?.parse()?)?;
+                    if meta.path.is_ident("min") {
+                        rules.minimum = Some(value);
+                    } else if meta.path.is_ident("max") {
+                        rules.maximum = Some(value);
+                    } else {
+                        return Err(meta.error("expected min or max"));
+                    }
+                    Ok(())
+                })
+            } else if meta.path.is_ident("regex") {
+                let pattern: LitStr = meta.value()?.parse()?;
+                if let Err(e) = regex::Regex::new(&pattern.value()) {
+                    // the last line of a syntax error is the reason, the
+                    // ones before point into the pattern
+                    let e = e.to_string();
+                    let reason = e.lines().last().unwrap_or_default();
+                    return Err(syn::Error::new(
+                        pattern.span(),
+                        format!("invalid regex: {}", reason.trim_start_matches("error: ")),
+                    ));
+                }
+                rules.pattern = Some(pattern);
+                Ok(())
+            } else if meta.path.is_ident("email") {
+                rules.email = true;
+                Ok(())
+            } else if meta.path.is_ident("url") {
+                rules.url = true;
+                Ok(())
+            } else if meta.path.is_ident("custom") {
+                let value = meta.value()?;
+                let path = if value.peek(LitStr) {
+                    value.parse::<LitStr>()?.parse()?
+                } else {
+                    value.parse()?
+                };
+                rules.custom.push(path);
+                Ok(())
+            } else {
+                Err(meta.error("expected length, range, regex, email, url or custom"))
+            }
+        })?;
+    }
+    if let (Some(min), Some(max)) = (rules.min_length, rules.max_length) {
+        if min > max {
+            return Err(syn::Error::new(span, "length min exceeds max"));
+        }
+    }
+    if let (Some(min), Some(max)) = (rules.minimum, rules.maximum) {
+        if min > max {
+            return Err(syn::Error::new(span, "range min exceeds max"));
+        }
+    }
+    if rules.email && rules.url {
+        return Err(syn::Error::new(
+            span,
+            "a field can't be validated as both email and url",
+        ));
+    }
+    Ok(rules)
+}
+
+fn option<T: quote::ToTokens>(value: Option<T>) -> TokenStream {
+    match value {
+        Some(value) => quote!(Some(#value)),
+        None => quote!(None),
+    }
+}
+
+pub(crate) fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let DeriveInput {
+        attrs,
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+    let fields = match data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            fields => {
+                return Err(syn::Error::new(
+                    fields.span(),
+                    "Validate requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                ident.span(),
+                "Validate can only be derived for structs",
+            ))
+        }
+    };
+    let rename_all = rename_all(&attrs)?;
+    let mut checks = Vec::new();
+    let mut constraints = Vec::new();
+    for field in &fields {
+        let rules = field_rules(&field.attrs, field.span())?;
+        if rules.is_empty() {
+            continue;
+        }
+        let field_ident = field.ident.as_ref().expect("named field");
+        let name = serde_field(&field.attrs)?.name(field_ident, rename_all.as_ref())?;
+        let path = format!("/{}", name.replace('~', "~0").replace('/', "~1"));
+        let span = field.span();
+
+        if rules.min_length.is_some() || rules.max_length.is_some() {
+            let min = option(rules.min_length.map(Literal::usize_suffixed));
+            let max = option(rules.max_length.map(Literal::usize_suffixed));
+            checks.push(quote_spanned! {span=>
+                rweb_helper::validation::check_length(#path, &self.#field_ident, #min, #max, &mut violations);
+            });
+        }
+        if rules.minimum.is_some() || rules.maximum.is_some() {
+            let min = option(rules.minimum.map(Literal::f64_suffixed));
+            let max = option(rules.maximum.map(Literal::f64_suffixed));
+            checks.push(quote_spanned! {span=>
+                rweb_helper::validation::check_range(#path, &self.#field_ident, #min, #max, &mut violations);
+            });
+        }
+        if let Some(pattern) = &rules.pattern {
+            checks.push(quote_spanned! {span=>
+                {
+                    static PATTERN: rweb_helper::validation::Pattern =
+                        rweb_helper::validation::Pattern::new(#pattern);
+                    rweb_helper::validation::check_pattern(#path, &self.#field_ident, &PATTERN, &mut violations);
+                }
+            });
+        }
+        if rules.email {
+            checks.push(quote_spanned! {span=>
+                rweb_helper::validation::check_email(#path, &self.#field_ident, &mut violations);
+            });
+        }
+        if rules.url {
+            checks.push(quote_spanned! {span=>
+                rweb_helper::validation::check_url(#path, &self.#field_ident, &mut violations);
+            });
+        }
+        for custom in &rules.custom {
+            checks.push(quote_spanned! {custom.span()=>
+                if let Err(message) = #custom(&self.#field_ident) {
+                    violations.push(rweb_helper::schema_validation::Violation::new(#path, message));
+                }
+            });
+        }
+
+        let min_length = option(rules.min_length.map(Literal::usize_suffixed));
+        let max_length = option(rules.max_length.map(Literal::usize_suffixed));
+        let minimum = option(rules.minimum.map(Literal::f64_suffixed));
+        let maximum = option(rules.maximum.map(Literal::f64_suffixed));
+        let pattern = option(rules.pattern.as_ref());
+        let format = if rules.email {
+            quote!(Some("email"))
+        } else if rules.url {
+            quote!(Some("uri"))
+        } else {
+            quote!(None)
+        };
+        constraints.push(quote! {
+            (
+                #name,
+                rweb_helper::validation::Constraints {
+                    min_length: #min_length,
+                    max_length: #max_length,
+                    minimum: #minimum,
+                    maximum: #maximum,
+                    pattern: #pattern,
+                    format: #format,
+                },
+            )
+        });
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics rweb_helper::validation::Validate for #ident #ty_generics #where_clause {
+            fn validate(&self) -> Result<(), rweb_helper::validation::ValidationError> {
+                #[allow(unused_mut)]
+                let mut violations: Vec<rweb_helper::schema_validation::Violation> = Vec::new();
+                #(#checks)*
+                if violations.is_empty() {
+                    Ok(())
+                } else {
+                    Err(rweb_helper::validation::ValidationError::new(violations))
+                }
+            }
+
+            fn constraints() -> Vec<(&'static str, rweb_helper::validation::Constraints)> {
+                vec![#(#constraints),*]
+            }
+        }
+    })
+}
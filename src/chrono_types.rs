@@ -0,0 +1,123 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, SecondsFormat, Utc};
+use derive_more::{Deref, From, Into};
+use rweb::openapi::{ComponentDescriptor, ComponentOrInlineSchema, Entity, Schema, Type};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    borrow::Cow,
+    fmt::{self, Formatter},
+    str::FromStr,
+};
+
+use crate::normalize_rfc3339;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DATE_FORMAT_BASIC: &str = "%Y%m%d";
+
+fn date_time_schema() -> ComponentOrInlineSchema {
+    ComponentOrInlineSchema::Inline(Schema {
+        schema_type: Some(Type::String),
+        format: Cow::Borrowed("date-time"),
+        example: Some(serde_json::value::Value::String(
+            "2023-01-01T00:00:00Z".into(),
+        )),
+        ..Schema::default()
+    })
+}
+
+/// Timestamp serialized as RFC 3339, e.g. `2023-01-01T00:00:00Z`.
+#[derive(Into, From, Deref, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DateTimeType(DateTime<Utc>);
+
+impl FromStr for DateTimeType {
+    type Err = chrono::ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DateTime::parse_from_rfc3339(&normalize_rfc3339(s)).map(|dt| Self(dt.with_timezone(&Utc)))
+    }
+}
+
+impl fmt::Display for DateTimeType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.0.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+}
+
+string_serde!(DateTimeType);
+
+impl Entity for DateTimeType {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("date-time")
+    }
+
+    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        date_time_schema()
+    }
+}
+
+/// Timestamp that keeps the offset it was given, serialized as RFC 3339.
+#[derive(Into, From, Deref, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DateTimeOffsetType(DateTime<FixedOffset>);
+
+impl FromStr for DateTimeOffsetType {
+    type Err = chrono::ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DateTime::parse_from_rfc3339(&normalize_rfc3339(s)).map(Self)
+    }
+}
+
+impl fmt::Display for DateTimeOffsetType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.0.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+}
+
+string_serde!(DateTimeOffsetType);
+
+impl Entity for DateTimeOffsetType {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("date-time")
+    }
+
+    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        date_time_schema()
+    }
+}
+
+/// Calendar date serialized as `YYYY-MM-DD`.
+#[derive(Into, From, Deref, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DateType(NaiveDate);
+
+impl FromStr for DateType {
+    type Err = chrono::ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        NaiveDate::parse_from_str(s, DATE_FORMAT)
+            .or_else(|e| NaiveDate::parse_from_str(s, DATE_FORMAT_BASIC).map_err(|_| e))
+            .map(Self)
+    }
+}
+
+impl fmt::Display for DateType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.format(DATE_FORMAT))
+    }
+}
+
+string_serde!(DateType);
+
+impl Entity for DateType {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("date")
+    }
+
+    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Inline(Schema {
+            schema_type: Some(Type::String),
+            format: Self::type_name(),
+            example: Some(serde_json::value::Value::String("2023-01-01".into())),
+            ..Schema::default()
+        })
+    }
+}
@@ -0,0 +1,233 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use rweb::{
+    http::StatusCode,
+    hyper::{Body, Response},
+    openapi::{
+        ComponentDescriptor, ComponentOrInlineSchema, Entity, ResponseEntity, Responses, Schema,
+        Type,
+    },
+    reject::Reject,
+    Rejection, Reply,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::Sha256;
+use std::{
+    borrow::Cow,
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Opaque pagination cursor.
+///
+/// The sort key is serialized as json and encoded as url-safe base64.  A
+/// signed cursor carries an HMAC-SHA256 of the payload after a `.`, so
+/// clients can't forge or modify it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct Cursor(String);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CursorError {
+    InvalidEncoding,
+    InvalidSignature,
+    MissingSignature,
+    UnexpectedSignature,
+    InvalidKey(String),
+}
+
+impl Display for CursorError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidEncoding => f.write_str("cursor is not valid url-safe base64"),
+            Self::InvalidSignature => f.write_str("cursor signature does not match"),
+            Self::MissingSignature => f.write_str("cursor is not signed"),
+            Self::UnexpectedSignature => {
+                f.write_str("cursor is signed and can only be decoded with its secret")
+            }
+            Self::InvalidKey(e) => write!(f, "cursor does not contain a valid key: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CursorError {}
+
+impl Reject for CursorError {}
+
+impl Cursor {
+    /// Encode `key` as an unsigned cursor.
+    ///
+    /// # Errors
+    /// Returns an error if `key` can't be serialized
+    pub fn encode<K: Serialize>(key: &K) -> Result<Self, CursorError> {
+        let payload =
+            serde_json::to_vec(key).map_err(|e| CursorError::InvalidKey(e.to_string()))?;
+        Ok(Self(URL_SAFE_NO_PAD.encode(payload)))
+    }
+
+    /// Encode `key` and sign it with `secret`.
+    ///
+    /// # Errors
+    /// Returns an error if `key` can't be serialized
+    pub fn encode_signed<K: Serialize>(key: &K, secret: &[u8]) -> Result<Self, CursorError> {
+        let Self(payload) = Self::encode(key)?;
+        let signature = URL_SAFE_NO_PAD.encode(sign(payload.as_bytes(), secret));
+        Ok(Self(format!("{}.{}", payload, signature)))
+    }
+
+    /// Decode the sort key of an unsigned cursor.
+    ///
+    /// # Errors
+    /// Returns an error if the cursor is signed or doesn't decode to `K`
+    pub fn decode<K: DeserializeOwned>(&self) -> Result<K, CursorError> {
+        match self.split() {
+            (payload, None) => decode_payload(payload),
+            (_, Some(_)) => Err(CursorError::UnexpectedSignature),
+        }
+    }
+
+    /// Verify the signature with `secret` and decode the sort key.
+    ///
+    /// # Errors
+    /// Returns an error if the signature is missing or doesn't match, or if
+    /// the cursor doesn't decode to `K`
+    pub fn decode_signed<K: DeserializeOwned>(&self, secret: &[u8]) -> Result<K, CursorError> {
+        let (payload, signature) = self.split();
+        let signature = signature.ok_or(CursorError::MissingSignature)?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| CursorError::InvalidEncoding)?;
+        let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts any key length");
+        mac.update(payload.as_bytes());
+        mac.verify_slice(&signature)
+            .map_err(|_| CursorError::InvalidSignature)?;
+        decode_payload(payload)
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    fn split(&self) -> (&str, Option<&str>) {
+        match self.0.split_once('.') {
+            Some((payload, signature)) => (payload, Some(signature)),
+            None => (&self.0, None),
+        }
+    }
+}
+
+fn sign(payload: &[u8], secret: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(payload);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn decode_payload<K: DeserializeOwned>(payload: &str) -> Result<K, CursorError> {
+    let payload = URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|_| CursorError::InvalidEncoding)?;
+    serde_json::from_slice(&payload).map_err(|e| CursorError::InvalidKey(e.to_string()))
+}
+
+impl FromStr for Cursor {
+    type Err = CursorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_base64 = |s: &str| {
+            !s.is_empty()
+                && s.bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+        };
+        let valid = match s.split_once('.') {
+            Some((payload, signature)) => is_base64(payload) && is_base64(signature),
+            None => is_base64(s),
+        };
+        if valid {
+            Ok(Self(s.into()))
+        } else {
+            Err(CursorError::InvalidEncoding)
+        }
+    }
+}
+
+impl TryFrom<String> for Cursor {
+    type Error = CursorError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Cursor> for String {
+    fn from(cursor: Cursor) -> Self {
+        cursor.0
+    }
+}
+
+impl Display for Cursor {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Entity for Cursor {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("cursor")
+    }
+
+    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Inline(Schema {
+            description: Cow::Borrowed("Opaque pagination cursor"),
+            schema_type: Some(Type::String),
+            ..Schema::default()
+        })
+    }
+}
+
+impl Reply for CursorError {
+    fn into_response(self) -> Response<Body> {
+        let mut res =
+            rweb::reply::json(&serde_json::json!({"error": self.to_string()})).into_response();
+        *res.status_mut() = StatusCode::BAD_REQUEST;
+        res
+    }
+}
+
+/// `recover` handler turning a [`CursorError`] rejection into its 400
+/// response, e.g. `route.recover(cursor::recover)`.
+///
+/// # Errors
+/// Passes through every other rejection
+pub async fn recover(rejection: Rejection) -> Result<CursorError, Rejection> {
+    match rejection.find::<CursorError>() {
+        Some(e) => Ok(e.clone()),
+        None => Err(rejection),
+    }
+}
+
+impl Entity for CursorError {
+    fn type_name() -> Cow<'static, str> {
+        rweb::http::Error::type_name()
+    }
+    fn describe(comp_d: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        rweb::http::Error::describe(comp_d)
+    }
+}
+
+impl ResponseEntity for CursorError {
+    fn describe_responses(_: &mut ComponentDescriptor) -> Responses {
+        let mut map = Responses::new();
+        map.insert(
+            Cow::Borrowed("400"),
+            rweb::openapi::Response {
+                description: Cow::Borrowed("Invalid cursor"),
+                ..rweb::openapi::Response::default()
+            },
+        );
+        map
+    }
+}
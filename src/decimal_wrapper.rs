@@ -0,0 +1,304 @@
+use derive_more::{Deref, Display, From, FromStr, Into};
+#[cfg(feature = "arbitrary-precision")]
+use rust_decimal::serde::arbitrary_precision as number_serde;
+#[cfg(not(feature = "arbitrary-precision"))]
+use rust_decimal::serde::float as number_serde;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use rweb::openapi::{ComponentDescriptor, ComponentOrInlineSchema, Entity, Schema, Type};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    borrow::Cow,
+    convert::TryFrom,
+    fmt::{self, Formatter},
+};
+
+use crate::validation::number;
+
+#[derive(
+    Into,
+    From,
+    Serialize,
+    Deserialize,
+    Deref,
+    Clone,
+    Copy,
+    Debug,
+    Hash,
+    PartialEq,
+    Eq,
+    FromStr,
+    Display,
+)]
+pub struct DecimalWrapper(Decimal);
+
+impl Entity for DecimalWrapper {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("decimal")
+    }
+
+    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Inline(Schema {
+            schema_type: Some(Type::String),
+            format: Self::type_name(),
+            example: Some(serde_json::value::Value::String("1.234".to_string())),
+            ..Default::default()
+        })
+    }
+}
+
+impl PartialEq<Decimal> for DecimalWrapper {
+    fn eq(&self, other: &Decimal) -> bool {
+        &self.0 == other
+    }
+}
+
+impl PartialEq<DecimalWrapper> for Decimal {
+    fn eq(&self, other: &DecimalWrapper) -> bool {
+        self == &other.0
+    }
+}
+
+/// `Decimal` serialized as a json number, documented as `type: number`.
+///
+/// Values go through `f64` unless the `arbitrary-precision` feature is
+/// enabled.  That feature turns on `serde_json/arbitrary_precision`, which
+/// applies to every crate using `serde_json` in the same build.
+#[derive(Into, From, Deref, Clone, Copy, Debug, Hash, PartialEq, Eq, FromStr, Display)]
+pub struct DecimalNumber(Decimal);
+
+impl Serialize for DecimalNumber {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        number_serde::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DecimalNumber {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        number_serde::deserialize(deserializer).map(Self)
+    }
+}
+
+impl Entity for DecimalNumber {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("decimal-number")
+    }
+
+    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Inline(Schema {
+            schema_type: Some(Type::Number),
+            example: serde_json::Number::from_f64(1.234).map(serde_json::Value::Number),
+            ..Default::default()
+        })
+    }
+}
+
+/// Error building one of the scaled decimal types.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecimalLimitError {
+    Parse(rust_decimal::Error),
+    Scale {
+        value: Decimal,
+        scale: u32,
+    },
+    Precision {
+        value: Decimal,
+        precision: u32,
+        scale: u32,
+    },
+}
+
+impl fmt::Display for DecimalLimitError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Parse(e) => write!(f, "{}", e),
+            Self::Scale { value, scale } => {
+                write!(f, "{} has more than {} decimal places", value, scale)
+            }
+            Self::Precision {
+                value,
+                precision,
+                scale,
+            } => write!(
+                f,
+                "{} does not fit in {} digits with {} decimal places",
+                value, precision, scale
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DecimalLimitError {}
+
+/// Largest value with `precision` digits, `scale` of them after the point.
+fn max_value(precision: u32, scale: u32) -> Decimal {
+    Decimal::from_i128_with_scale(10_i128.pow(precision) - 1, scale)
+}
+
+/// Check `value` against the limits and rescale it to exactly `scale`
+/// decimal places.
+fn limit(value: Decimal, precision: u32, scale: u32) -> Result<Decimal, DecimalLimitError> {
+    if value.normalize().scale() > scale {
+        return Err(DecimalLimitError::Scale { value, scale });
+    }
+    if value.abs() > max_value(precision, scale) {
+        return Err(DecimalLimitError::Precision {
+            value,
+            precision,
+            scale,
+        });
+    }
+    let mut value = value;
+    value.rescale(scale);
+    Ok(value)
+}
+
+/// Document the bounds and step of a `NUMERIC(precision, scale)` value.
+///
+/// The schema stores these as json numbers, so they are the nearest `f64`
+/// values: `maximum` may round for large precisions and `multipleOf` is only
+/// an approximation of `10^-scale`.
+fn limit_schema(schema: &mut Schema, precision: u32, scale: u32) {
+    let max = max_value(precision, scale).to_f64().unwrap_or(f64::MAX);
+    let step = Decimal::new(1, scale).to_f64().unwrap_or(1.0);
+    schema.multiple_of = number(step);
+    schema.minimum = number(-max);
+    schema.maximum = number(max);
+}
+
+/// Document a `NUMERIC(precision, scale)` value serialized as a string.
+///
+/// `minimum`, `maximum` and `multipleOf` don't apply to strings, so the
+/// limits are a `pattern` on the digits and a `maxLength`.
+fn limit_string_schema(schema: &mut Schema, precision: u32, scale: u32) {
+    let integer_digits = (precision - scale).max(1);
+    let mut pattern = format!("^-?[0-9]{{1,{}}}", integer_digits);
+    let mut max_length = 1 + integer_digits;
+    if scale > 0 {
+        pattern.push_str(&format!("(\\.[0-9]{{1,{}}})?", scale));
+        max_length += 1 + scale;
+    }
+    pattern.push('$');
+    schema.description = Cow::Owned(format!(
+        "Decimal with at most {} digits, {} of them after the point",
+        precision, scale
+    ));
+    schema.pattern = Some(pattern.into());
+    schema.max_length = Some(max_length as _);
+}
+
+macro_rules! scaled_decimal {
+    (
+        $(#[$meta:meta])* $T:ident,
+        $serialize:expr,
+        $deserialize:expr,
+        $schema:expr,
+        $limit_schema:expr
+    ) => {
+        $(#[$meta])*
+        #[derive(Deref, Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Display)]
+        pub struct $T<const PRECISION: u32, const SCALE: u32>(Decimal);
+
+        impl<const PRECISION: u32, const SCALE: u32> $T<PRECISION, SCALE> {
+            const LIMITS: () = assert!(
+                PRECISION > 0 && PRECISION <= 28 && SCALE <= PRECISION,
+                "precision must be in 1..=28 and scale can't exceed it"
+            );
+
+            /// # Errors
+            /// Returns an error if `value` has more than `SCALE` decimal
+            /// places or more than `PRECISION` digits
+            pub fn new(value: Decimal) -> Result<Self, DecimalLimitError> {
+                let () = Self::LIMITS;
+                limit(value, PRECISION, SCALE).map(Self)
+            }
+        }
+
+        impl<const PRECISION: u32, const SCALE: u32> TryFrom<Decimal> for $T<PRECISION, SCALE> {
+            type Error = DecimalLimitError;
+
+            fn try_from(value: Decimal) -> Result<Self, Self::Error> {
+                Self::new(value)
+            }
+        }
+
+        impl<const PRECISION: u32, const SCALE: u32> From<$T<PRECISION, SCALE>> for Decimal {
+            fn from(value: $T<PRECISION, SCALE>) -> Self {
+                value.0
+            }
+        }
+
+        impl<const PRECISION: u32, const SCALE: u32> std::str::FromStr for $T<PRECISION, SCALE> {
+            type Err = DecimalLimitError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.trim()
+                    .parse()
+                    .map_err(DecimalLimitError::Parse)
+                    .and_then(Self::new)
+            }
+        }
+
+        impl<const PRECISION: u32, const SCALE: u32> Serialize for $T<PRECISION, SCALE> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                ($serialize)(&self.0, serializer)
+            }
+        }
+
+        impl<'de, const PRECISION: u32, const SCALE: u32> Deserialize<'de>
+            for $T<PRECISION, SCALE>
+        {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                Self::new(($deserialize)(deserializer)?).map_err(serde::de::Error::custom)
+            }
+        }
+
+        impl<const PRECISION: u32, const SCALE: u32> Entity for $T<PRECISION, SCALE> {
+            fn type_name() -> Cow<'static, str> {
+                let () = Self::LIMITS;
+                Cow::Owned(format!("{}_{}_{}", stringify!($T), PRECISION, SCALE))
+            }
+
+            fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+                let () = Self::LIMITS;
+                let mut example = Decimal::ONE;
+                example.rescale(SCALE);
+                let mut schema = Schema {
+                    example: serde_json::to_value(Self(example)).ok(),
+                    ..$schema
+                };
+                ($limit_schema)(&mut schema, PRECISION, SCALE);
+                ComponentOrInlineSchema::Inline(schema)
+            }
+        }
+    };
+}
+
+scaled_decimal!(
+    /// Decimal serialized as a string with at most `PRECISION` digits,
+    /// `SCALE` of them after the point, e.g. `ScaledDecimal<12, 2>` for money.
+    ///
+    /// Values are rescaled to exactly `SCALE` decimal places, and the limits
+    /// are documented as a `pattern` and `maxLength`.
+    ScaledDecimal,
+    <Decimal as Serialize>::serialize,
+    <Decimal as Deserialize>::deserialize,
+    Schema {
+        schema_type: Some(Type::String),
+        format: Cow::Borrowed("decimal"),
+        ..Default::default()
+    },
+    limit_string_schema
+);
+
+scaled_decimal!(
+    /// [`ScaledDecimal`] serialized as a json number like [`DecimalNumber`],
+    /// with the limits documented as `multipleOf`, `minimum` and `maximum`.
+    ScaledDecimalNumber,
+    number_serde::serialize,
+    number_serde::deserialize,
+    Schema {
+        schema_type: Some(Type::Number),
+        ..Default::default()
+    },
+    limit_schema
+);
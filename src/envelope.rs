@@ -0,0 +1,234 @@
+use rweb::openapi::{
+    ComponentDescriptor, ComponentOrInlineSchema, Entity, ResponseEntity, Schema, Type,
+};
+use serde::{
+    de::{DeserializeOwned, Error},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_json::{Map, Value};
+use std::{borrow::Cow, marker::PhantomData, time::Duration};
+
+#[cfg(feature = "uuid")]
+use crate::UuidWrapper;
+use crate::{cursor::Cursor, json_response::JsonResponse, pagination::Paginated};
+
+/// Pagination details reported in the envelope `meta`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, rweb::Schema)]
+pub struct PageMeta {
+    pub total: usize,
+    pub limit: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<Cursor>,
+    pub next: Option<String>,
+    pub prev: Option<String>,
+}
+
+/// Request scoped metadata reported next to the payload.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, rweb::Schema)]
+pub struct EnvelopeMeta {
+    #[cfg(feature = "uuid")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<UuidWrapper>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(description = "Time spent handling the request in milliseconds")]
+    pub elapsed_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<PageMeta>,
+}
+
+impl EnvelopeMeta {
+    #[cfg(feature = "uuid")]
+    #[must_use]
+    pub fn with_request_id(mut self, request_id: impl Into<UuidWrapper>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_elapsed(mut self, elapsed: Duration) -> Self {
+        self.elapsed_ms = Some(elapsed.as_millis() as u64);
+        self
+    }
+
+    #[must_use]
+    pub fn with_pagination(mut self, pagination: PageMeta) -> Self {
+        self.pagination = Some(pagination);
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, rweb::Schema)]
+pub struct EnvelopeError {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    pub message: String,
+}
+
+/// Field names of an [`Envelope`].
+///
+/// ```ignore
+/// struct ResultKeys;
+///
+/// impl EnvelopeKeys for ResultKeys {
+///     const DATA: &'static str = "result";
+///     const META: &'static str = "metadata";
+///     const ERRORS: &'static str = "problems";
+/// }
+///
+/// let envelope = Envelope::<_, ResultKeys>::keyed(item);
+/// ```
+pub trait EnvelopeKeys {
+    const DATA: &'static str;
+    const META: &'static str;
+    const ERRORS: &'static str;
+}
+
+/// The `data` / `meta` / `errors` field names.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DefaultKeys;
+
+impl EnvelopeKeys for DefaultKeys {
+    const DATA: &'static str = "data";
+    const META: &'static str = "meta";
+    const ERRORS: &'static str = "errors";
+}
+
+/// `{ "data": ..., "meta": ..., "errors": [...] }` wrapper around a payload.
+///
+/// The field names come from `K`, see [`EnvelopeKeys`]; a custom `K` needs
+/// to derive `Clone`, `Debug` and `PartialEq` like [`DefaultKeys`] for the
+/// envelope to implement them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Envelope<T, K = DefaultKeys> {
+    pub data: T,
+    pub meta: EnvelopeMeta,
+    pub errors: Vec<EnvelopeError>,
+    keys: PhantomData<K>,
+}
+
+impl<T> Envelope<T> {
+    pub fn new(data: T) -> Self {
+        Self::keyed(data)
+    }
+}
+
+impl<T, K> Envelope<T, K> {
+    /// Envelope using the field names of `K`.
+    pub fn keyed(data: T) -> Self {
+        Self {
+            data,
+            meta: EnvelopeMeta::default(),
+            errors: Vec::new(),
+            keys: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn with_meta(mut self, meta: EnvelopeMeta) -> Self {
+        self.meta = meta;
+        self
+    }
+
+    #[must_use]
+    pub fn with_error(mut self, error: EnvelopeError) -> Self {
+        self.errors.push(error);
+        self
+    }
+}
+
+impl<T, K> Serialize for Envelope<T, K>
+where
+    T: Serialize,
+    K: EnvelopeKeys,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let len = if self.errors.is_empty() { 2 } else { 3 };
+        let mut map = serializer.serialize_map(Some(len))?;
+        map.serialize_entry(K::DATA, &self.data)?;
+        map.serialize_entry(K::META, &self.meta)?;
+        if !self.errors.is_empty() {
+            map.serialize_entry(K::ERRORS, &self.errors)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, T, K> Deserialize<'de> for Envelope<T, K>
+where
+    T: DeserializeOwned,
+    K: EnvelopeKeys,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut map = Map::<String, Value>::deserialize(deserializer)?;
+        let mut field = |name: &'static str| map.remove(name);
+        let data = field(K::DATA).ok_or_else(|| D::Error::missing_field(K::DATA))?;
+        let meta = field(K::META).ok_or_else(|| D::Error::missing_field(K::META))?;
+        let errors = field(K::ERRORS);
+        Ok(Self {
+            data: serde_json::from_value(data).map_err(D::Error::custom)?,
+            meta: serde_json::from_value(meta).map_err(D::Error::custom)?,
+            errors: match errors {
+                Some(errors) => serde_json::from_value(errors).map_err(D::Error::custom)?,
+                None => Vec::new(),
+            },
+            keys: PhantomData,
+        })
+    }
+}
+
+impl<T> From<Paginated<T>> for Envelope<Vec<T>> {
+    fn from(page: Paginated<T>) -> Self {
+        let pagination = PageMeta {
+            total: page.total,
+            limit: page.limit,
+            offset: page.offset,
+            cursor: page.cursor,
+            next: page.next,
+            prev: page.prev,
+        };
+        Self::new(page.items).with_meta(EnvelopeMeta::default().with_pagination(pagination))
+    }
+}
+
+impl<T, K> Entity for Envelope<T, K>
+where
+    T: Entity,
+    K: EnvelopeKeys,
+{
+    fn type_name() -> Cow<'static, str> {
+        format!("Envelope_{}", T::type_name()).into()
+    }
+
+    fn describe(comp_d: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Inline(Schema {
+            schema_type: Some(Type::Object),
+            properties: vec![
+                (Cow::Borrowed(K::DATA), T::describe(comp_d)),
+                (Cow::Borrowed(K::META), EnvelopeMeta::describe(comp_d)),
+                (
+                    Cow::Borrowed(K::ERRORS),
+                    Vec::<EnvelopeError>::describe(comp_d),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            required: vec![Cow::Borrowed(K::DATA), Cow::Borrowed(K::META)],
+            ..Schema::default()
+        })
+    }
+}
+
+impl<T, K, E> JsonResponse<Envelope<T, K>, E>
+where
+    T: Serialize + Entity + Send,
+    K: EnvelopeKeys + Send,
+    E: ResponseEntity + Send,
+{
+    /// Wrap `data` in an envelope carrying `meta`.
+    pub fn enveloped(data: T, meta: EnvelopeMeta) -> Self {
+        Self::new(Envelope::keyed(data).with_meta(meta))
+    }
+}
@@ -0,0 +1,120 @@
+use rweb::{
+    hyper::Body,
+    openapi::{Entity, MediaTypeExample, Response, ResponseEntity},
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    html_response::HtmlResponse,
+    json_response::JsonResponse,
+    pagination::{Paginated, PaginatedResponse},
+    response_description_trait::ResponseDescriptionTrait,
+    schema_validation::validate,
+};
+
+/// Response types whose success body can be documented with
+/// `#[response(example = ...)]`.
+///
+/// The derive leaves an example that doesn't match the schema out of the
+/// spec and generates a test reporting the mismatch, so declare the response
+/// type outside of a function body for the test harness to pick it up.
+pub trait ResponseExample {
+    /// The type an example function has to return.
+    type Example;
+
+    /// Json value of an example.
+    ///
+    /// # Errors
+    /// Returns a description of the error if the example can't be serialized
+    fn example_value(example: &Self::Example) -> Result<Value, String>;
+
+    /// Check a json example against the documented body.
+    ///
+    /// # Errors
+    /// Returns a description of the mismatch
+    fn check_example(example: &Value) -> Result<(), String> {
+        let _ = example;
+        Ok(())
+    }
+}
+
+impl<T, E, D> ResponseExample for JsonResponse<T, E, D>
+where
+    T: Serialize + Entity + Send,
+    E: ResponseEntity + Send,
+    D: ResponseDescriptionTrait,
+{
+    type Example = T;
+
+    fn example_value(example: &T) -> Result<Value, String> {
+        serde_json::to_value(example).map_err(|e| format!("failed to serialize: {}", e))
+    }
+
+    fn check_example(example: &Value) -> Result<(), String> {
+        let violations = validate::<T>(example);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            let violations: Vec<String> = violations.iter().map(ToString::to_string).collect();
+            Err(violations.join("; "))
+        }
+    }
+}
+
+impl<T, E, D> ResponseExample for HtmlResponse<T, E, D>
+where
+    T: ResponseEntity + Send + AsRef<str>,
+    Body: From<T>,
+    E: ResponseEntity + Send,
+    D: ResponseDescriptionTrait,
+{
+    type Example = T;
+
+    fn example_value(example: &T) -> Result<Value, String> {
+        Ok(Value::String(example.as_ref().into()))
+    }
+
+    fn check_example(example: &Value) -> Result<(), String> {
+        if example.is_string() {
+            Ok(())
+        } else {
+            Err("expected a string".into())
+        }
+    }
+}
+
+impl<T, E> ResponseExample for PaginatedResponse<T, E>
+where
+    T: Serialize + Entity + Send,
+    E: ResponseEntity + Send,
+{
+    type Example = Paginated<T>;
+
+    fn example_value(example: &Paginated<T>) -> Result<Value, String> {
+        JsonResponse::<Paginated<T>, E>::example_value(example)
+    }
+
+    fn check_example(example: &Value) -> Result<(), String> {
+        JsonResponse::<Paginated<T>, E>::check_example(example)
+    }
+}
+
+/// Parse a json example checked by the `RwebResponse` derive.
+///
+/// # Errors
+/// Returns a description of the error if `example` isn't valid json
+#[doc(hidden)]
+pub fn parse_example(example: &str) -> Result<Value, String> {
+    serde_json::from_str(example).map_err(|e| format!("invalid json: {}", e))
+}
+
+/// Set `example` on every media type of a documented response.
+#[doc(hidden)]
+pub fn document_example(response: &mut Response, example: &Value) {
+    for media in response.content.values_mut() {
+        media.examples = Some(MediaTypeExample::Example {
+            example: example.clone(),
+        });
+    }
+}
@@ -0,0 +1,285 @@
+use rweb::{
+    filters::BoxedFilter,
+    http::StatusCode,
+    hyper::{Body, Response},
+    openapi::{
+        ComponentDescriptor, ComponentOrInlineSchema, Entity, ResponseEntity, Responses, Schema,
+        Type,
+    },
+    reject::Reject,
+    Filter, FromRequest, Rejection, Reply,
+};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+    marker::PhantomData,
+};
+
+use crate::schema_util::{object_properties, resolve_schema};
+
+const MAX_DEPTH: usize = 8;
+
+/// Sparse fieldset parsed from `?fields=a,b,c.d`.
+///
+/// The requested paths are checked against the OpenAPI schema of `T`; an
+/// empty fieldset means the whole value is returned.
+pub struct Fields<T> {
+    paths: Vec<Vec<String>>,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Fields<T> {
+    fn clone(&self) -> Self {
+        Self {
+            paths: self.paths.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Fields<T> {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    pub(crate) fn into_paths(self) -> Vec<Vec<String>> {
+        self.paths
+    }
+}
+
+impl<T> Fields<T>
+where
+    T: Entity,
+{
+    /// Parse a comma separated list of dotted paths.
+    ///
+    /// # Errors
+    /// Returns an error listing the valid field names if any path is not
+    /// part of the schema of `T`
+    pub fn parse(fields: &str) -> Result<Self, FieldsError> {
+        let requested: Vec<&str> = fields
+            .split(',')
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+            .collect();
+        let valid = valid_fields::<T>();
+        let unknown: Vec<String> = requested
+            .iter()
+            .filter(|f| !valid.iter().any(|v| v == *f))
+            .map(|f| (*f).to_string())
+            .collect();
+        if !unknown.is_empty() {
+            return Err(FieldsError { unknown, valid });
+        }
+        let paths = requested
+            .into_iter()
+            .map(|f| f.split('.').map(Into::into).collect())
+            .collect();
+        Ok(Self {
+            paths,
+            phantom: PhantomData,
+        })
+    }
+}
+
+/// All dotted field paths of the schema of `T`.
+#[must_use]
+pub fn valid_fields<T: Entity>() -> Vec<String> {
+    let mut comp_d = ComponentDescriptor::new();
+    let schema = T::describe(&mut comp_d);
+    let mut fields = Vec::new();
+    if let Some(schema) = resolve_schema(&comp_d, &schema) {
+        collect_fields(&comp_d, &schema, "", 0, &mut fields);
+    }
+    fields
+}
+
+fn collect_fields(
+    comp_d: &ComponentDescriptor,
+    schema: &Schema,
+    prefix: &str,
+    depth: usize,
+    fields: &mut Vec<String>,
+) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    if schema.schema_type == Some(Type::Array) {
+        if let Some(items) = schema
+            .items
+            .as_ref()
+            .and_then(|items| resolve_schema(comp_d, items))
+        {
+            collect_fields(comp_d, &items, prefix, depth + 1, fields);
+        }
+        return;
+    }
+    for (name, property) in object_properties(comp_d, schema) {
+        let path = if prefix.is_empty() {
+            name
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+        if let Some(property) = resolve_schema(comp_d, &property) {
+            collect_fields(comp_d, &property, &path, depth + 1, fields);
+        }
+        fields.push(path);
+    }
+}
+
+/// Keep only the given paths of `value`, descending into arrays.
+pub(crate) fn project(value: Value, paths: &[Vec<String>]) -> Value {
+    if paths.is_empty() || paths.iter().any(Vec::is_empty) {
+        return value;
+    }
+    match value {
+        Value::Array(items) => Value::Array(items.into_iter().map(|v| project(v, paths)).collect()),
+        Value::Object(mut map) => {
+            let mut projected = Map::new();
+            for path in paths {
+                let key = &path[0];
+                if projected.contains_key(key) {
+                    continue;
+                }
+                if let Some(v) = map.remove(key) {
+                    let sub_paths: Vec<Vec<String>> = paths
+                        .iter()
+                        .filter(|p| &p[0] == key)
+                        .map(|p| p[1..].to_vec())
+                        .collect();
+                    projected.insert(key.clone(), project(v, &sub_paths));
+                }
+            }
+            Value::Object(projected)
+        }
+        value => value,
+    }
+}
+
+#[derive(Deserialize)]
+struct FieldsQuery {
+    fields: Option<String>,
+}
+
+/// Filter extracting a validated `?fields=` projection for `T`.
+pub fn fields<T>() -> impl Filter<Extract = (Fields<T>,), Error = Rejection> + Clone
+where
+    T: Entity + 'static,
+{
+    rweb::filters::query::query::<FieldsQuery>().and_then(|query: FieldsQuery| async move {
+        match query.fields {
+            Some(fields) => Fields::<T>::parse(&fields).map_err(rweb::reject::custom),
+            None => Ok(Fields {
+                paths: Vec::new(),
+                phantom: PhantomData,
+            }),
+        }
+    })
+}
+
+impl<T> Entity for Fields<T>
+where
+    T: Entity,
+{
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("Fields")
+    }
+
+    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        let description = format!(
+            "Comma separated list of fields to return, one of: {}",
+            valid_fields::<T>().join(", ")
+        );
+        let fields = Schema {
+            description: description.into(),
+            schema_type: Some(Type::String),
+            ..Schema::default()
+        };
+        ComponentOrInlineSchema::Inline(Schema {
+            schema_type: Some(Type::Object),
+            properties: vec![(
+                Cow::Borrowed("fields"),
+                ComponentOrInlineSchema::Inline(fields),
+            )]
+            .into_iter()
+            .collect(),
+            ..Schema::default()
+        })
+    }
+}
+
+impl<T> FromRequest for Fields<T>
+where
+    T: Entity + Send + 'static,
+{
+    type Filter = BoxedFilter<(Self,)>;
+
+    fn is_query() -> bool {
+        true
+    }
+
+    fn new() -> Self::Filter {
+        fields::<T>().boxed()
+    }
+}
+
+/// Rejection returned when `?fields=` names fields that don't exist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldsError {
+    pub unknown: Vec<String>,
+    pub valid: Vec<String>,
+}
+
+impl Display for FieldsError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown fields: {}; valid fields are: {}",
+            self.unknown.join(", "),
+            self.valid.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for FieldsError {}
+
+impl Reject for FieldsError {}
+
+impl Reply for FieldsError {
+    fn into_response(self) -> Response<Body> {
+        let body = serde_json::json!({
+            "error": self.to_string(),
+            "unknown": self.unknown,
+            "valid": self.valid,
+        });
+        let mut res = rweb::reply::json(&body).into_response();
+        *res.status_mut() = StatusCode::BAD_REQUEST;
+        res
+    }
+}
+
+impl Entity for FieldsError {
+    fn type_name() -> Cow<'static, str> {
+        rweb::http::Error::type_name()
+    }
+    fn describe(comp_d: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        rweb::http::Error::describe(comp_d)
+    }
+}
+
+impl ResponseEntity for FieldsError {
+    fn describe_responses(_: &mut ComponentDescriptor) -> Responses {
+        let mut map = Responses::new();
+        map.insert(
+            Cow::Borrowed("400"),
+            rweb::openapi::Response {
+                description: Cow::Borrowed("Unknown field requested"),
+                ..rweb::openapi::Response::default()
+            },
+        );
+        map
+    }
+}
@@ -1,33 +1,46 @@
 use rweb::{
-    http::{header::SET_COOKIE, HeaderValue},
+    http::{
+        header::{HeaderName, SET_COOKIE},
+        HeaderValue,
+    },
     hyper::{Body, Response},
     openapi::{ComponentDescriptor, ComponentOrInlineSchema, Entity, ResponseEntity, Responses},
     Reply,
 };
 use std::{borrow::Cow, convert::TryFrom, marker::PhantomData};
 
-pub struct HtmlResponse<T, E>
+use crate::response_description_trait::{
+    describe_success, DefaultDescription, ResponseDescriptionTrait,
+};
+
+pub struct HtmlResponse<T, E, D = DefaultDescription>
 where
     T: ResponseEntity + Send,
     Body: From<T>,
     E: ResponseEntity + Send,
+    D: ResponseDescriptionTrait,
 {
     data: T,
     cookies: Option<Vec<String>>,
+    headers: Vec<(HeaderName, HeaderValue)>,
     phantom_e: PhantomData<E>,
+    phantom_d: PhantomData<D>,
 }
 
-impl<T, E> HtmlResponse<T, E>
+impl<T, E, D> HtmlResponse<T, E, D>
 where
     T: ResponseEntity + Send,
     Body: From<T>,
     E: ResponseEntity + Send,
+    D: ResponseDescriptionTrait,
 {
     pub fn new(data: T) -> Self {
         Self {
             data,
             cookies: None,
+            headers: Vec::new(),
             phantom_e: PhantomData,
+            phantom_d: PhantomData,
         }
     }
 
@@ -40,13 +53,20 @@ where
         }
         self
     }
+
+    #[must_use]
+    pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.push((name, value));
+        self
+    }
 }
 
-impl<T, E> Reply for HtmlResponse<T, E>
+impl<T, E, D> Reply for HtmlResponse<T, E, D>
 where
     T: ResponseEntity + Send,
     Body: From<T>,
     E: ResponseEntity + Send,
+    D: ResponseDescriptionTrait,
 {
     fn into_response(self) -> Response<Body> {
         let reply = rweb::reply::html(self.data);
@@ -58,15 +78,19 @@ where
                 }
             }
         }
+        for (name, value) in self.headers {
+            res.headers_mut().append(name, value);
+        }
         res
     }
 }
 
-impl<T, E> Entity for HtmlResponse<T, E>
+impl<T, E, D> Entity for HtmlResponse<T, E, D>
 where
     T: ResponseEntity + Send,
     Body: From<T>,
     E: ResponseEntity + Send,
+    D: ResponseDescriptionTrait,
 {
     fn type_name() -> Cow<'static, str> {
         Result::<T, E>::type_name()
@@ -76,13 +100,16 @@ where
     }
 }
 
-impl<T, E> ResponseEntity for HtmlResponse<T, E>
+impl<T, E, D> ResponseEntity for HtmlResponse<T, E, D>
 where
     T: ResponseEntity + Send,
     Body: From<T>,
     E: ResponseEntity + Send,
+    D: ResponseDescriptionTrait,
 {
     fn describe_responses(comp_d: &mut ComponentDescriptor) -> Responses {
-        Result::<T, E>::describe_responses(comp_d)
+        let mut resp = Result::<T, E>::describe_responses(comp_d);
+        describe_success::<D>(&mut resp);
+        resp
     }
 }
@@ -0,0 +1,560 @@
+use rweb::{
+    http::{header::CONTENT_TYPE, HeaderValue, StatusCode},
+    hyper::{Body, Response},
+    openapi::{
+        ComponentDescriptor, ComponentOrInlineSchema, Entity, MediaType, ResponseEntity, Responses,
+        Schema, Type,
+    },
+    reject::Reject,
+    Json, Reply,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{borrow::Cow, collections::BTreeMap, marker::PhantomData};
+
+use crate::{schema_util::resolve_schema, UuidWrapper};
+
+pub const JSON_API_CONTENT_TYPE: &str = "application/vnd.api+json";
+
+/// A type that can be rendered as a JSON:API resource object.
+///
+/// The field named by `id_field` is moved out of `attributes` into the
+/// top level `id` member.
+pub trait JsonApiResource: Serialize + Entity + Send {
+    fn resource_type() -> &'static str;
+
+    fn resource_id(&self) -> UuidWrapper;
+
+    fn id_field() -> &'static str {
+        "id"
+    }
+
+    fn relationships(&self) -> BTreeMap<String, Relationship> {
+        BTreeMap::new()
+    }
+
+    fn links(&self) -> BTreeMap<String, String> {
+        BTreeMap::new()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ResourceIdentifier {
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    pub id: UuidWrapper,
+}
+
+impl ResourceIdentifier {
+    pub fn new<T: JsonApiResource>(id: impl Into<UuidWrapper>) -> Self {
+        Self {
+            resource_type: T::resource_type().into(),
+            id: id.into(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum RelationshipData {
+    Many(Vec<ResourceIdentifier>),
+    One(Option<ResourceIdentifier>),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Relationship {
+    pub data: RelationshipData,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub links: BTreeMap<String, String>,
+}
+
+impl Relationship {
+    #[must_use]
+    pub fn one(identifier: Option<ResourceIdentifier>) -> Self {
+        Self {
+            data: RelationshipData::One(identifier),
+            links: BTreeMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn many(identifiers: Vec<ResourceIdentifier>) -> Self {
+        Self {
+            data: RelationshipData::Many(identifiers),
+            links: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ResourceObject {
+    pub id: UuidWrapper,
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    pub attributes: Value,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub relationships: BTreeMap<String, Relationship>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub links: BTreeMap<String, String>,
+}
+
+impl ResourceObject {
+    pub fn from_resource<T: JsonApiResource>(resource: &T) -> Result<Self, serde_json::Error> {
+        let mut attributes = serde_json::to_value(resource)?;
+        if let Value::Object(map) = &mut attributes {
+            map.remove(T::id_field());
+        }
+        Ok(Self {
+            id: resource.resource_id(),
+            resource_type: T::resource_type().into(),
+            attributes,
+            relationships: resource.relationships(),
+            links: resource.links(),
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct Document<D> {
+    data: D,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    included: Vec<ResourceObject>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    links: BTreeMap<String, String>,
+}
+
+/// Reply with the document built from `data`, or with a 500 error document
+/// when a resource failed to serialize.
+fn document_reply<D: Serialize>(
+    data: Result<D, serde_json::Error>,
+    included: Vec<ResourceObject>,
+    links: BTreeMap<String, String>,
+) -> Response<Body> {
+    match data {
+        Ok(data) => {
+            let document = Document {
+                data,
+                included,
+                links,
+            };
+            json_api_reply(&document, StatusCode::OK)
+        }
+        Err(e) => JsonApiErrors::from(JsonApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            e.to_string(),
+        ))
+        .into_response(),
+    }
+}
+
+fn json_api_reply(document: &impl Serialize, status: StatusCode) -> Response<Body> {
+    let mut res = rweb::reply::json(document).into_response();
+    res.headers_mut().insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static(JSON_API_CONTENT_TYPE),
+    );
+    *res.status_mut() = status;
+    res
+}
+
+/// Object whose values all follow `values`.
+fn map_schema(values: Schema) -> Schema {
+    Schema {
+        schema_type: Some(Type::Object),
+        additional_properties: Some(Box::new(ComponentOrInlineSchema::Inline(values))),
+        ..Schema::default()
+    }
+}
+
+fn links_schema() -> Schema {
+    map_schema(Schema {
+        schema_type: Some(Type::String),
+        ..Schema::default()
+    })
+}
+
+fn relationship_schema(comp_d: &mut ComponentDescriptor) -> Schema {
+    let identifier = ComponentOrInlineSchema::Inline(Schema {
+        schema_type: Some(Type::Object),
+        properties: vec![
+            (
+                Cow::Borrowed("type"),
+                ComponentOrInlineSchema::Inline(Schema {
+                    schema_type: Some(Type::String),
+                    ..Schema::default()
+                }),
+            ),
+            (Cow::Borrowed("id"), UuidWrapper::describe(comp_d)),
+        ]
+        .into_iter()
+        .collect(),
+        required: vec![Cow::Borrowed("type"), Cow::Borrowed("id")],
+        ..Schema::default()
+    });
+    let data = Schema {
+        description: Cow::Borrowed(
+            "A resource identifier, null, or an array of resource identifiers",
+        ),
+        one_of: vec![
+            identifier.clone(),
+            ComponentOrInlineSchema::Inline(Schema {
+                schema_type: Some(Type::Array),
+                items: Some(Box::new(identifier)),
+                ..Schema::default()
+            }),
+        ],
+        nullable: Some(true),
+        ..Schema::default()
+    };
+    Schema {
+        schema_type: Some(Type::Object),
+        properties: vec![
+            (Cow::Borrowed("data"), ComponentOrInlineSchema::Inline(data)),
+            (
+                Cow::Borrowed("links"),
+                ComponentOrInlineSchema::Inline(links_schema()),
+            ),
+        ]
+        .into_iter()
+        .collect(),
+        required: vec![Cow::Borrowed("data")],
+        ..Schema::default()
+    }
+}
+
+fn resource_schema<T: JsonApiResource>(comp_d: &mut ComponentDescriptor) -> Schema {
+    let described = T::describe(comp_d);
+    let mut attributes = resolve_schema(comp_d, &described).unwrap_or_default();
+    attributes.properties.remove(T::id_field());
+    attributes.required.retain(|r| r != T::id_field());
+    let resource_type = Schema {
+        schema_type: Some(Type::String),
+        enum_values: vec![T::resource_type().into()],
+        ..Schema::default()
+    };
+    Schema {
+        schema_type: Some(Type::Object),
+        properties: vec![
+            (Cow::Borrowed("id"), UuidWrapper::describe(comp_d)),
+            (
+                Cow::Borrowed("type"),
+                ComponentOrInlineSchema::Inline(resource_type),
+            ),
+            (
+                Cow::Borrowed("attributes"),
+                ComponentOrInlineSchema::Inline(attributes),
+            ),
+            (
+                Cow::Borrowed("relationships"),
+                ComponentOrInlineSchema::Inline(map_schema(relationship_schema(comp_d))),
+            ),
+            (
+                Cow::Borrowed("links"),
+                ComponentOrInlineSchema::Inline(links_schema()),
+            ),
+        ]
+        .into_iter()
+        .collect(),
+        required: vec![
+            Cow::Borrowed("id"),
+            Cow::Borrowed("type"),
+            Cow::Borrowed("attributes"),
+        ],
+        ..Schema::default()
+    }
+}
+
+fn document_schema(data: ComponentOrInlineSchema) -> Schema {
+    let included = Schema {
+        schema_type: Some(Type::Array),
+        items: Some(Box::new(ComponentOrInlineSchema::Inline(Schema {
+            schema_type: Some(Type::Object),
+            ..Schema::default()
+        }))),
+        ..Schema::default()
+    };
+    Schema {
+        schema_type: Some(Type::Object),
+        properties: vec![
+            (Cow::Borrowed("data"), data),
+            (
+                Cow::Borrowed("included"),
+                ComponentOrInlineSchema::Inline(included),
+            ),
+            (
+                Cow::Borrowed("links"),
+                ComponentOrInlineSchema::Inline(links_schema()),
+            ),
+        ]
+        .into_iter()
+        .collect(),
+        required: vec![Cow::Borrowed("data")],
+        ..Schema::default()
+    }
+}
+
+/// Move the documented json body of the success responses to the JSON:API
+/// media type.
+fn json_api_responses(mut resp: Responses) -> Responses {
+    for (code, response) in resp.iter_mut() {
+        if !code.starts_with('2') {
+            continue;
+        }
+        let old_content_type: Cow<'static, str> = "application/json".into();
+        if let Some(content) = response.content.remove(&old_content_type) {
+            response
+                .content
+                .insert(JSON_API_CONTENT_TYPE.into(), content);
+        }
+    }
+    resp
+}
+
+/// A JSON:API document whose primary data is a single resource object.
+pub struct JsonApiResponse<T, E>
+where
+    T: JsonApiResource,
+{
+    data: T,
+    included: Vec<ResourceObject>,
+    links: BTreeMap<String, String>,
+    phantom_e: PhantomData<E>,
+}
+
+impl<T, E> JsonApiResponse<T, E>
+where
+    T: JsonApiResource,
+    E: ResponseEntity + Send,
+{
+    pub fn new(data: T) -> Self {
+        Self {
+            data,
+            included: Vec::new(),
+            links: BTreeMap::new(),
+            phantom_e: PhantomData,
+        }
+    }
+
+    pub fn with_included<R: JsonApiResource>(
+        mut self,
+        resource: &R,
+    ) -> Result<Self, serde_json::Error> {
+        self.included.push(ResourceObject::from_resource(resource)?);
+        Ok(self)
+    }
+
+    #[must_use]
+    pub fn with_link(mut self, name: impl Into<String>, href: impl Into<String>) -> Self {
+        self.links.insert(name.into(), href.into());
+        self
+    }
+}
+
+impl<T, E> Reply for JsonApiResponse<T, E>
+where
+    T: JsonApiResource,
+    E: ResponseEntity + Send,
+{
+    fn into_response(self) -> Response<Body> {
+        let data = ResourceObject::from_resource(&self.data);
+        document_reply(data, self.included, self.links)
+    }
+}
+
+impl<T, E> Entity for JsonApiResponse<T, E>
+where
+    T: JsonApiResource,
+    E: ResponseEntity + Send,
+{
+    fn type_name() -> Cow<'static, str> {
+        format!("JsonApi_{}", T::type_name()).into()
+    }
+
+    fn describe(comp_d: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        let data = ComponentOrInlineSchema::Inline(resource_schema::<T>(comp_d));
+        ComponentOrInlineSchema::Inline(document_schema(data))
+    }
+}
+
+impl<T, E> ResponseEntity for JsonApiResponse<T, E>
+where
+    T: JsonApiResource,
+    E: ResponseEntity + Send,
+{
+    fn describe_responses(comp_d: &mut ComponentDescriptor) -> Responses {
+        json_api_responses(Result::<Json<Self>, E>::describe_responses(comp_d))
+    }
+}
+
+/// A JSON:API document whose primary data is a list of resource objects.
+pub struct JsonApiListResponse<T, E>
+where
+    T: JsonApiResource,
+{
+    data: Vec<T>,
+    included: Vec<ResourceObject>,
+    links: BTreeMap<String, String>,
+    phantom_e: PhantomData<E>,
+}
+
+impl<T, E> JsonApiListResponse<T, E>
+where
+    T: JsonApiResource,
+    E: ResponseEntity + Send,
+{
+    pub fn new(data: Vec<T>) -> Self {
+        Self {
+            data,
+            included: Vec::new(),
+            links: BTreeMap::new(),
+            phantom_e: PhantomData,
+        }
+    }
+
+    pub fn with_included<R: JsonApiResource>(
+        mut self,
+        resource: &R,
+    ) -> Result<Self, serde_json::Error> {
+        self.included.push(ResourceObject::from_resource(resource)?);
+        Ok(self)
+    }
+
+    #[must_use]
+    pub fn with_link(mut self, name: impl Into<String>, href: impl Into<String>) -> Self {
+        self.links.insert(name.into(), href.into());
+        self
+    }
+}
+
+impl<T, E> Reply for JsonApiListResponse<T, E>
+where
+    T: JsonApiResource,
+    E: ResponseEntity + Send,
+{
+    fn into_response(self) -> Response<Body> {
+        let data = self
+            .data
+            .iter()
+            .map(ResourceObject::from_resource)
+            .collect::<Result<Vec<_>, _>>();
+        document_reply(data, self.included, self.links)
+    }
+}
+
+impl<T, E> Entity for JsonApiListResponse<T, E>
+where
+    T: JsonApiResource,
+    E: ResponseEntity + Send,
+{
+    fn type_name() -> Cow<'static, str> {
+        format!("JsonApiList_{}", T::type_name()).into()
+    }
+
+    fn describe(comp_d: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        let data = ComponentOrInlineSchema::Inline(Schema {
+            schema_type: Some(Type::Array),
+            items: Some(Box::new(ComponentOrInlineSchema::Inline(
+                resource_schema::<T>(comp_d),
+            ))),
+            ..Schema::default()
+        });
+        ComponentOrInlineSchema::Inline(document_schema(data))
+    }
+}
+
+impl<T, E> ResponseEntity for JsonApiListResponse<T, E>
+where
+    T: JsonApiResource,
+    E: ResponseEntity + Send,
+{
+    fn describe_responses(comp_d: &mut ComponentDescriptor) -> Responses {
+        json_api_responses(Result::<Json<Self>, E>::describe_responses(comp_d))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, rweb::Schema)]
+pub struct JsonApiErrorSource {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pointer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameter: Option<String>,
+}
+
+/// JSON:API error object.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, rweb::Schema)]
+pub struct JsonApiError {
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<JsonApiErrorSource>,
+}
+
+impl JsonApiError {
+    #[must_use]
+    pub fn new(status: StatusCode, detail: impl Into<String>) -> Self {
+        Self {
+            status: status.as_u16().to_string(),
+            title: status.canonical_reason().map(Into::into),
+            detail: Some(detail.into()),
+            ..Self::default()
+        }
+    }
+}
+
+/// JSON:API error document, `{"errors": [...]}`.
+///
+/// The response status is taken from the first error.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, rweb::Schema)]
+pub struct JsonApiErrors {
+    pub errors: Vec<JsonApiError>,
+}
+
+impl From<JsonApiError> for JsonApiErrors {
+    fn from(error: JsonApiError) -> Self {
+        Self {
+            errors: vec![error],
+        }
+    }
+}
+
+impl Reject for JsonApiErrors {}
+
+impl Reply for JsonApiErrors {
+    fn into_response(self) -> Response<Body> {
+        let status = self
+            .errors
+            .first()
+            .and_then(|e| e.status.parse().ok())
+            .and_then(|s| StatusCode::from_u16(s).ok())
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        json_api_reply(&self, status)
+    }
+}
+
+impl ResponseEntity for JsonApiErrors {
+    fn describe_responses(comp_d: &mut ComponentDescriptor) -> Responses {
+        let mut map = Responses::new();
+        map.insert(
+            Cow::Borrowed("default"),
+            rweb::openapi::Response {
+                description: Cow::Borrowed("JSON:API error document"),
+                content: vec![(
+                    Cow::Borrowed(JSON_API_CONTENT_TYPE),
+                    MediaType {
+                        schema: Some(Self::describe(comp_d)),
+                        ..MediaType::default()
+                    },
+                )]
+                .into_iter()
+                .collect(),
+                ..rweb::openapi::Response::default()
+            },
+        );
+        map
+    }
+}
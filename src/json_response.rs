@@ -1,5 +1,8 @@
 use rweb::{
-    http::{header::SET_COOKIE, HeaderValue},
+    http::{
+        header::{HeaderName, SET_COOKIE},
+        HeaderValue,
+    },
     hyper::{Body, Response},
     openapi::{ComponentDescriptor, ComponentOrInlineSchema, Entity, ResponseEntity, Responses},
     Json, Reply,
@@ -8,25 +11,37 @@ use serde::Serialize;
 use std::convert::TryFrom;
 use std::marker::PhantomData;
 
-pub struct JsonResponse<T, E>
+use crate::{
+    fields::{project, Fields},
+    response_description_trait::{describe_success, DefaultDescription, ResponseDescriptionTrait},
+};
+
+pub struct JsonResponse<T, E, D = DefaultDescription>
 where
     T: Serialize + Entity + Send,
 {
     data: T,
     cookies: Option<Vec<String>>,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    fields: Option<Vec<Vec<String>>>,
     phantom_e: PhantomData<E>,
+    phantom_d: PhantomData<D>,
 }
 
-impl<T, E> JsonResponse<T, E>
+impl<T, E, D> JsonResponse<T, E, D>
 where
     T: Serialize + Entity + Send,
     E: ResponseEntity + Send,
+    D: ResponseDescriptionTrait,
 {
     pub fn new(data: T) -> Self {
         Self {
             data,
             cookies: None,
+            headers: Vec::new(),
+            fields: None,
             phantom_e: PhantomData,
+            phantom_d: PhantomData,
         }
     }
 
@@ -39,15 +54,37 @@ where
         }
         self
     }
+
+    #[must_use]
+    pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.push((name, value));
+        self
+    }
+
+    /// Only serialize the fields requested through `?fields=`.
+    #[must_use]
+    pub fn with_fields(mut self, fields: Fields<T>) -> Self {
+        if !fields.is_empty() {
+            self.fields = Some(fields.into_paths());
+        }
+        self
+    }
 }
 
-impl<T, E> Reply for JsonResponse<T, E>
+impl<T, E, D> Reply for JsonResponse<T, E, D>
 where
     T: Serialize + Entity + Send,
     E: ResponseEntity + Send,
+    D: ResponseDescriptionTrait,
 {
     fn into_response(self) -> Response<Body> {
-        let reply = rweb::reply::json(&self.data);
+        let reply = match &self.fields {
+            Some(fields) => match serde_json::to_value(&self.data) {
+                Ok(value) => rweb::reply::json(&project(value, fields)),
+                Err(_) => rweb::reply::json(&self.data),
+            },
+            None => rweb::reply::json(&self.data),
+        };
         let mut res = reply.into_response();
         if let Some(cookies) = self.cookies {
             for cookie in cookies {
@@ -56,14 +93,18 @@ where
                 }
             }
         }
+        for (name, value) in self.headers {
+            res.headers_mut().append(name, value);
+        }
         res
     }
 }
 
-impl<T, E> Entity for JsonResponse<T, E>
+impl<T, E, D> Entity for JsonResponse<T, E, D>
 where
     T: Serialize + Entity + Send,
     E: ResponseEntity + Send,
+    D: ResponseDescriptionTrait,
 {
     fn type_name() -> std::borrow::Cow<'static, str> {
         Result::<T, E>::type_name()
@@ -73,12 +114,15 @@ where
     }
 }
 
-impl<T, E> ResponseEntity for JsonResponse<T, E>
+impl<T, E, D> ResponseEntity for JsonResponse<T, E, D>
 where
     T: Serialize + Entity + Send,
     E: ResponseEntity + Send,
+    D: ResponseDescriptionTrait,
 {
     fn describe_responses(comp_d: &mut ComponentDescriptor) -> Responses {
-        Result::<Json<T>, E>::describe_responses(comp_d)
+        let mut resp = Result::<Json<T>, E>::describe_responses(comp_d);
+        describe_success::<D>(&mut resp);
+        resp
     }
 }
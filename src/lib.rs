@@ -1,11 +1,84 @@
+/// Serialize through `Display` and deserialize through `FromStr`.
+#[allow(unused_macros)]
+macro_rules! string_serde {
+    ($T:ty) => {
+        impl Serialize for $T {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $T {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                String::deserialize(deserializer)?
+                    .parse()
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "chrono")]
+pub mod chrono_types;
 pub mod content_type_trait;
+pub mod cursor;
+#[cfg(feature = "decimal")]
+pub mod decimal_wrapper;
+pub mod envelope;
+pub mod example;
+pub mod fields;
 pub mod html_response;
+#[cfg(feature = "uuid")]
+pub mod json_api;
 pub mod json_response;
+pub mod links;
+pub mod pagination;
+#[cfg(feature = "postgres")]
+mod postgres;
+pub mod query;
 pub mod response_description_trait;
+#[doc(hidden)]
+pub mod schema_as;
+mod schema_util;
+pub mod schema_validation;
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-sqlite"))]
+mod sqlx_types;
 pub mod status_code_trait;
+#[cfg(feature = "time")]
+pub mod time_types;
+#[cfg(feature = "uuid")]
+pub mod uuid_wrapper;
+pub mod validation;
+
+pub use rweb_helper_macro::{RwebResponse, RwebSchemaAs};
+
+#[cfg(all(feature = "chrono", not(feature = "time")))]
+pub use chrono_types::{DateTimeType, DateType};
+#[cfg(feature = "decimal")]
+pub use decimal_wrapper::{DecimalNumber, DecimalWrapper, ScaledDecimal, ScaledDecimalNumber};
+#[cfg(feature = "time")]
+pub use time_types::*;
+#[cfg(feature = "uuid")]
+pub use uuid_wrapper::UuidWrapper;
+
+/// Marker implemented by `#[derive(RwebSchemaAs)]`: the serialized form of
+/// `Self` matches the schema of `T`.
+///
+/// The derive requires it between each field and the field of the target it
+/// mirrors; implement it for other pairs of types serialized the same way.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not documented with the schema of `{T}`",
+    label = "this field does not match the target field of type `{T}`"
+)]
+pub trait SchemaCompatible<T: ?Sized> {}
 
-pub use rweb_helper_macro::RwebResponse;
+impl<T: ?Sized> SchemaCompatible<T> for T {}
 
+/// Document `$T0` with the schema of `$T1`.
+///
+/// Only the sizes of the two types are compared; prefer
+/// `#[derive(RwebSchemaAs)]`, which checks the field types against `$T1` at
+/// compile time and the serialized field names in a generated test.
 #[macro_export]
 macro_rules! derive_rweb_schema {
     ($T0:ty, $T1:ty) => {
@@ -30,137 +103,33 @@ macro_rules! derive_rweb_test {
     };
 }
 
-use derive_more::{Deref, Display, From, FromStr, Into};
-use once_cell::sync::Lazy;
-use rust_decimal::Decimal;
-use rweb::openapi::{ComponentDescriptor, ComponentOrInlineSchema, Entity, Schema, Type};
-use serde::{Deserialize, Serialize};
-use std::{borrow::Cow, str::FromStr};
-use time::{Date, OffsetDateTime};
-use uuid::Uuid;
-
-static UUID_EXAMPLE: Lazy<Uuid> =
-    Lazy::new(|| Uuid::from_str("334518f4-1bfd-4f20-9978-bfad0dc033e1").unwrap());
-
-#[derive(Into, From, Serialize, Deserialize, Deref, Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct DateTimeType(OffsetDateTime);
-
-impl Entity for DateTimeType {
-    fn type_name() -> Cow<'static, str> {
-        Cow::Borrowed("date-time")
-    }
-
-    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
-        ComponentOrInlineSchema::Inline(Schema {
-            schema_type: Some(Type::String),
-            format: Self::type_name(),
-            ..Schema::default()
-        })
-    }
-}
-
-#[derive(Into, From, Serialize, Deserialize, Deref, Clone, Copy, Debug, Hash, PartialEq, Eq)]
-pub struct DateType(Date);
-
-impl Entity for DateType {
-    fn type_name() -> Cow<'static, str> {
-        Cow::Borrowed("date")
-    }
-
-    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
-        ComponentOrInlineSchema::Inline(Schema {
-            schema_type: Some(Type::String),
-            format: Self::type_name(),
-            example: Some(serde_json::value::Value::String("2023-01-01".into())),
-            ..Schema::default()
-        })
-    }
-}
-
-#[derive(
-    Into,
-    From,
-    Serialize,
-    Deserialize,
-    Deref,
-    Clone,
-    Copy,
-    Debug,
-    Hash,
-    PartialEq,
-    Eq,
-    FromStr,
-    Display,
-)]
-pub struct UuidWrapper(Uuid);
-
-impl Entity for UuidWrapper {
-    fn type_name() -> Cow<'static, str> {
-        Cow::Borrowed("uuid")
-    }
-
-    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
-        ComponentOrInlineSchema::Inline(Schema {
-            schema_type: Some(Type::String),
-            format: Self::type_name(),
-            example: Some(serde_json::value::Value::String(UUID_EXAMPLE.to_string())),
-            ..Default::default()
-        })
-    }
-}
-
-impl PartialEq<Uuid> for UuidWrapper {
-    fn eq(&self, other: &Uuid) -> bool {
-        &self.0 == other
-    }
-}
-
-impl PartialEq<UuidWrapper> for Uuid {
-    fn eq(&self, other: &UuidWrapper) -> bool {
-        self == &other.0
-    }
-}
-
-#[derive(
-    Into,
-    From,
-    Serialize,
-    Deserialize,
-    Deref,
-    Clone,
-    Copy,
-    Debug,
-    Hash,
-    PartialEq,
-    Eq,
-    FromStr,
-    Display,
-)]
-pub struct DecimalWrapper(Decimal);
-
-impl Entity for DecimalWrapper {
-    fn type_name() -> Cow<'static, str> {
-        Cow::Borrowed("decimal")
-    }
-
-    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
-        ComponentOrInlineSchema::Inline(Schema {
-            schema_type: Some(Type::String),
-            format: Self::type_name(),
-            example: Some(serde_json::value::Value::String("1.234".to_string())),
-            ..Default::default()
-        })
+/// Rewrite the common near-RFC 3339 spellings (`2023-01-01 00:00:00z`,
+/// `+0000`, `+00`) into the strict form.
+#[cfg(any(feature = "time", feature = "chrono"))]
+pub(crate) fn normalize_rfc3339(s: &str) -> String {
+    let mut normalized: Vec<u8> = s.trim().bytes().collect();
+    if let Some(b) = normalized.get_mut(10) {
+        if *b == b' ' || *b == b't' {
+            *b = b'T';
+        }
     }
-}
-
-impl PartialEq<Decimal> for DecimalWrapper {
-    fn eq(&self, other: &Decimal) -> bool {
-        &self.0 == other
+    if let Some(b) = normalized.last_mut() {
+        if *b == b'z' {
+            *b = b'Z';
+        }
     }
-}
-
-impl PartialEq<DecimalWrapper> for Decimal {
-    fn eq(&self, other: &DecimalWrapper) -> bool {
-        self == &other.0
+    if let Some(sign) = normalized
+        .iter()
+        .skip(11)
+        .rposition(|b| *b == b'+' || *b == b'-')
+        .map(|i| i + 11)
+    {
+        let offset = &normalized[sign + 1..];
+        if offset.len() == 4 && offset.iter().all(u8::is_ascii_digit) {
+            normalized.insert(sign + 3, b':');
+        } else if offset.len() == 2 && offset.iter().all(u8::is_ascii_digit) {
+            normalized.extend_from_slice(b":00");
+        }
     }
+    String::from_utf8_lossy(&normalized).into_owned()
 }
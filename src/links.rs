@@ -0,0 +1,260 @@
+use rweb::{
+    http::Method,
+    openapi::{ComponentDescriptor, ComponentOrInlineSchema, Entity, Schema, Spec, Type},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+};
+
+use crate::schema_util::resolve_schema;
+
+/// A single hypermedia link.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, rweb::Schema)]
+pub struct Link {
+    pub href: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+impl Link {
+    pub fn new(href: impl Into<String>) -> Self {
+        Self {
+            href: href.into(),
+            method: None,
+            title: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_method(mut self, method: &Method) -> Self {
+        self.method = Some(method.as_str().into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+/// Links keyed by relation name, serialized as `_links`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Links(BTreeMap<String, Link>);
+
+impl Links {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with(mut self, rel: impl Into<String>, link: Link) -> Self {
+        self.0.insert(rel.into(), link);
+        self
+    }
+
+    pub fn insert(&mut self, rel: impl Into<String>, link: Link) -> Option<Link> {
+        self.0.insert(rel.into(), link)
+    }
+
+    #[must_use]
+    pub fn get(&self, rel: &str) -> Option<&Link> {
+        self.0.get(rel)
+    }
+}
+
+impl Entity for Links {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("Links")
+    }
+
+    fn describe(comp_d: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Inline(Schema {
+            schema_type: Some(Type::Object),
+            additional_properties: Some(Box::new(Link::describe(comp_d))),
+            ..Schema::default()
+        })
+    }
+}
+
+/// Wrapper adding a `_links` member to the serialized json of `T`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WithLinks<T> {
+    #[serde(flatten)]
+    pub inner: T,
+    #[serde(rename = "_links")]
+    pub links: Links,
+}
+
+impl<T> WithLinks<T> {
+    pub fn new(inner: T, links: Links) -> Self {
+        Self { inner, links }
+    }
+}
+
+impl<T> Entity for WithLinks<T>
+where
+    T: Entity,
+{
+    fn type_name() -> Cow<'static, str> {
+        format!("WithLinks_{}", T::type_name()).into()
+    }
+
+    fn describe(comp_d: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        let inner = T::describe(comp_d);
+        let mut schema = resolve_schema(comp_d, &inner).unwrap_or_default();
+        let links = Links::describe(comp_d);
+        schema.properties.insert(Cow::Borrowed("_links"), links);
+        schema.required.push(Cow::Borrowed("_links"));
+        ComponentOrInlineSchema::Inline(schema)
+    }
+}
+
+/// A route template such as `/items/{id}` and its method.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Route {
+    pub method: Method,
+    pub path: String,
+}
+
+impl Route {
+    pub fn new(method: Method, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+        }
+    }
+
+    /// The route registered by a `#[get]`/`#[post]`/... handler, e.g.
+    /// `Route::from_handler(get_item)`, so links use the declared path
+    /// instead of a copy of it.
+    ///
+    /// This builds an OpenAPI spec for the handler, so call it once (e.g. at
+    /// startup) and not while another spec is being built.
+    pub fn from_handler<F, R>(handler: F) -> Option<Self>
+    where
+        F: FnOnce() -> R,
+    {
+        let (spec, _) = rweb::openapi::spec().build(handler);
+        Routes::from_spec(&spec).0.into_iter().next()
+    }
+
+    /// Fill in the `{name}` segments of the template.
+    ///
+    /// # Errors
+    /// Returns an error naming the segments missing from `params`
+    pub fn link(&self, params: &[(&str, &dyn Display)]) -> Result<Link, LinkError> {
+        let mut href = String::with_capacity(self.path.len());
+        let mut missing = Vec::new();
+        let mut rest = self.path.as_str();
+        while let Some((before, after)) = rest.split_once('{') {
+            let (name, after) = match after.split_once('}') {
+                Some(segment) => segment,
+                None => break,
+            };
+            href.push_str(before);
+            match params.iter().find(|(param, _)| *param == name) {
+                Some((_, value)) => href.push_str(&encode_segment(&value.to_string())),
+                None => missing.push(name.to_string()),
+            }
+            rest = after;
+        }
+        href.push_str(rest);
+        if missing.is_empty() {
+            Ok(Link::new(href).with_method(&self.method))
+        } else {
+            Err(LinkError {
+                path: self.path.clone(),
+                missing,
+            })
+        }
+    }
+}
+
+/// Error returned when a route template has segments without a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkError {
+    pub path: String,
+    pub missing: Vec<String>,
+}
+
+impl Display for LinkError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "no value for {} in {}",
+            self.missing.join(", "),
+            self.path
+        )
+    }
+}
+
+impl std::error::Error for LinkError {}
+
+fn encode_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for b in segment.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+            encoded.push(b as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", b));
+        }
+    }
+    encoded
+}
+
+/// Method of an operation key in an OpenAPI path item; other keys such as
+/// `parameters`, `summary` or `servers` are not operations.
+pub(crate) fn operation_method(key: &str) -> Option<Method> {
+    match key {
+        "get" => Some(Method::GET),
+        "put" => Some(Method::PUT),
+        "post" => Some(Method::POST),
+        "delete" => Some(Method::DELETE),
+        "options" => Some(Method::OPTIONS),
+        "head" => Some(Method::HEAD),
+        "patch" => Some(Method::PATCH),
+        "trace" => Some(Method::TRACE),
+        _ => None,
+    }
+}
+
+/// Routes documented in an OpenAPI spec.
+///
+/// Looking up a route that is no longer part of the spec returns `None`; use
+/// [`Route::from_handler`] to build links without repeating the path.
+#[derive(Clone, Debug, Default)]
+pub struct Routes(Vec<Route>);
+
+impl Routes {
+    #[must_use]
+    pub fn from_spec(spec: &Spec) -> Self {
+        let mut routes = Vec::new();
+        let spec = serde_json::to_value(spec).unwrap_or_default();
+        if let Some(paths) = spec.get("paths").and_then(|p| p.as_object()) {
+            for (path, item) in paths {
+                if let Some(item) = item.as_object() {
+                    for key in item.keys() {
+                        if let Some(method) = operation_method(key) {
+                            routes.push(Route::new(method, path.clone()));
+                        }
+                    }
+                }
+            }
+        }
+        Self(routes)
+    }
+
+    #[must_use]
+    pub fn get(&self, method: &Method, path: &str) -> Option<&Route> {
+        self.0
+            .iter()
+            .find(|route| &route.method == method && route.path == path)
+    }
+}
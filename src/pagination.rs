@@ -0,0 +1,385 @@
+use rweb::{
+    filters::BoxedFilter,
+    http::{
+        header::{HeaderName, InvalidHeaderValue, LINK},
+        HeaderValue,
+    },
+    hyper::{Body, Response},
+    openapi::{
+        ComponentDescriptor, ComponentOrInlineSchema, Entity, Header, ObjectOrReference,
+        ResponseEntity, Responses, Schema, Type,
+    },
+    Filter, FromRequest, Rejection, Reply,
+};
+use serde::{Deserialize, Serialize};
+use std::{borrow::Cow, convert::TryFrom};
+
+use crate::{
+    cursor::Cursor,
+    json_response::JsonResponse,
+    query::{parse_query, percent_decode},
+};
+
+pub const DEFAULT_LIMIT: usize = 20;
+pub const MAX_LIMIT: usize = 1000;
+
+/// Query parameters accepted by paginated list endpoints.
+///
+/// As a handler argument it is extracted with [`pagination_query`] and
+/// documents `limit`, `offset` and `cursor` as query parameters.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PaginationQuery {
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    pub cursor: Option<Cursor>,
+}
+
+impl PaginationQuery {
+    /// Requested page size, clamped to `1..=MAX_LIMIT`.
+    #[must_use]
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+    }
+
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.offset.unwrap_or(0)
+    }
+}
+
+impl Entity for PaginationQuery {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("PaginationQuery")
+    }
+
+    fn describe(comp_d: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        let limit = Schema {
+            description: Cow::Borrowed("Maximum number of items to return"),
+            schema_type: Some(Type::Integer),
+            minimum: Some(1.into()),
+            maximum: Some((MAX_LIMIT as i64).into()),
+            default: Some((DEFAULT_LIMIT as i64).into()),
+            ..Schema::default()
+        };
+        let offset = Schema {
+            description: Cow::Borrowed("Number of items to skip"),
+            schema_type: Some(Type::Integer),
+            minimum: Some(0.into()),
+            default: Some(0.into()),
+            ..Schema::default()
+        };
+        ComponentOrInlineSchema::Inline(Schema {
+            schema_type: Some(Type::Object),
+            properties: vec![
+                (
+                    Cow::Borrowed("limit"),
+                    ComponentOrInlineSchema::Inline(limit),
+                ),
+                (
+                    Cow::Borrowed("offset"),
+                    ComponentOrInlineSchema::Inline(offset),
+                ),
+                (Cow::Borrowed("cursor"), Cursor::describe(comp_d)),
+            ]
+            .into_iter()
+            .collect(),
+            ..Schema::default()
+        })
+    }
+}
+
+impl FromRequest for PaginationQuery {
+    type Filter = BoxedFilter<(Self,)>;
+
+    fn is_query() -> bool {
+        true
+    }
+
+    fn new() -> Self::Filter {
+        pagination_query().boxed()
+    }
+}
+
+/// Parse a raw query string into a [`PaginationQuery`].
+///
+/// # Errors
+/// Returns a [`CursorError`](crate::cursor::CursorError) rejection for a
+/// malformed `cursor` and a [`QueryError`](crate::query::QueryError)
+/// rejection for anything else, including a `limit` of 0
+pub fn parse_pagination_query(query: &str) -> Result<PaginationQuery, Rejection> {
+    for pair in query.split('&') {
+        let mut parts = pair.splitn(2, '=');
+        if percent_decode(parts.next().unwrap_or_default()) == "cursor" {
+            percent_decode(parts.next().unwrap_or_default())
+                .parse::<Cursor>()
+                .map_err(rweb::reject::custom)?;
+        }
+    }
+    parse_query(query).map_err(rweb::reject::custom)
+}
+
+/// Filter extracting [`PaginationQuery`], rejecting a malformed cursor with
+/// a [`CursorError`](crate::cursor::CursorError) instead of the generic
+/// invalid query rejection.
+///
+/// Pair it with [`cursor::recover`](crate::cursor::recover) to send the 400.
+pub fn pagination_query() -> impl Filter<Extract = (PaginationQuery,), Error = Rejection> + Clone {
+    rweb::filters::query::raw()
+        .or(rweb::filters::any::any().map(String::new))
+        .unify()
+        .and_then(|query: String| async move { parse_pagination_query(&query) })
+}
+
+/// A single page of a list endpoint.
+///
+/// `next` and `prev` hold the urls of the neighbouring pages, if any.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    pub total: usize,
+    pub limit: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<Cursor>,
+    pub next: Option<String>,
+    pub prev: Option<String>,
+}
+
+impl<T> Paginated<T> {
+    /// Build an offset/limit page; `base_path` is the path of the list
+    /// endpoint, used to build the `next` and `prev` urls.
+    pub fn from_offset(
+        items: Vec<T>,
+        total: usize,
+        query: &PaginationQuery,
+        base_path: &str,
+    ) -> Self {
+        let limit = query.limit();
+        let offset = query.offset();
+        let next = if offset.saturating_add(limit) < total {
+            Some(offset_url(base_path, limit, offset.saturating_add(limit)))
+        } else {
+            None
+        };
+        let prev = if offset > 0 {
+            Some(offset_url(base_path, limit, offset.saturating_sub(limit)))
+        } else {
+            None
+        };
+        Self {
+            items,
+            total,
+            limit,
+            offset: Some(offset),
+            cursor: None,
+            next,
+            prev,
+        }
+    }
+
+    /// Build a cursor based page from the cursors pointing at the
+    /// neighbouring pages.
+    pub fn from_cursor(
+        items: Vec<T>,
+        total: usize,
+        query: &PaginationQuery,
+        next_cursor: Option<&Cursor>,
+        prev_cursor: Option<&Cursor>,
+        base_path: &str,
+    ) -> Self {
+        let limit = query.limit();
+        Self {
+            items,
+            total,
+            limit,
+            offset: None,
+            cursor: query.cursor.clone(),
+            next: next_cursor.map(|c| cursor_url(base_path, limit, c)),
+            prev: prev_cursor.map(|c| cursor_url(base_path, limit, c)),
+        }
+    }
+
+    /// Page size used for the `first` and `last` links; a `limit` of 0 is
+    /// treated as 1, matching [`PaginationQuery::limit`].
+    fn link_limit(&self) -> usize {
+        self.limit.max(1)
+    }
+
+    fn first(&self, base_path: &str) -> String {
+        let limit = self.link_limit();
+        if self.offset.is_some() {
+            offset_url(base_path, limit, 0)
+        } else {
+            format!("{}?limit={}", base_path, limit)
+        }
+    }
+
+    fn last(&self, base_path: &str) -> Option<String> {
+        self.offset.map(|_| {
+            let limit = self.link_limit();
+            let last = if self.total == 0 {
+                0
+            } else {
+                (self.total - 1) / limit * limit
+            };
+            offset_url(base_path, limit, last)
+        })
+    }
+}
+
+fn offset_url(base_path: &str, limit: usize, offset: usize) -> String {
+    format!("{}?limit={}&offset={}", base_path, limit, offset)
+}
+
+fn cursor_url(base_path: &str, limit: usize, cursor: &Cursor) -> String {
+    format!("{}?limit={}&cursor={}", base_path, limit, cursor)
+}
+
+impl<T> Entity for Paginated<T>
+where
+    T: Entity,
+{
+    fn type_name() -> Cow<'static, str> {
+        format!("Paginated_{}", T::type_name()).into()
+    }
+
+    fn describe(comp_d: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Inline(Schema {
+            schema_type: Some(Type::Object),
+            properties: vec![
+                (Cow::Borrowed("items"), Vec::<T>::describe(comp_d)),
+                (Cow::Borrowed("total"), usize::describe(comp_d)),
+                (Cow::Borrowed("limit"), usize::describe(comp_d)),
+                (Cow::Borrowed("offset"), Option::<usize>::describe(comp_d)),
+                (Cow::Borrowed("cursor"), Option::<Cursor>::describe(comp_d)),
+                (Cow::Borrowed("next"), Option::<String>::describe(comp_d)),
+                (Cow::Borrowed("prev"), Option::<String>::describe(comp_d)),
+            ]
+            .into_iter()
+            .collect(),
+            required: vec![
+                Cow::Borrowed("items"),
+                Cow::Borrowed("total"),
+                Cow::Borrowed("limit"),
+            ],
+            ..Schema::default()
+        })
+    }
+}
+
+/// Json list response that also emits RFC 8288 `Link` headers and
+/// `X-Total-Count`.
+pub struct PaginatedResponse<T, E>
+where
+    T: Serialize + Entity + Send,
+{
+    link: HeaderValue,
+    total: usize,
+    inner: JsonResponse<Paginated<T>, E>,
+}
+
+impl<T, E> PaginatedResponse<T, E>
+where
+    T: Serialize + Entity + Send,
+    E: ResponseEntity + Send,
+{
+    /// Build the response and its `Link` header.
+    ///
+    /// # Errors
+    /// Returns an error if the page urls can't be sent in a header, e.g.
+    /// because `base_path` contains control characters
+    pub fn new(page: Paginated<T>, base_path: &str) -> Result<Self, InvalidHeaderValue> {
+        let mut links = Vec::new();
+        if let Some(next) = &page.next {
+            links.push(("next", next.clone()));
+        }
+        if let Some(prev) = &page.prev {
+            links.push(("prev", prev.clone()));
+        }
+        links.push(("first", page.first(base_path)));
+        if let Some(last) = page.last(base_path) {
+            links.push(("last", last));
+        }
+        let link = links
+            .iter()
+            .map(|(rel, url)| format!("<{}>; rel=\"{}\"", url, rel))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let link = <HeaderValue as TryFrom<String>>::try_from(link)?;
+        Ok(Self {
+            link,
+            total: page.total,
+            inner: JsonResponse::new(page),
+        })
+    }
+
+    #[must_use]
+    pub fn with_cookie(mut self, cookie: impl Into<String>) -> Self {
+        self.inner = self.inner.with_cookie(cookie);
+        self
+    }
+}
+
+impl<T, E> Reply for PaginatedResponse<T, E>
+where
+    T: Serialize + Entity + Send,
+    E: ResponseEntity + Send,
+{
+    fn into_response(self) -> Response<Body> {
+        let mut res = self.inner.into_response();
+        res.headers_mut().insert(LINK, self.link);
+        res.headers_mut().insert(
+            HeaderName::from_static("x-total-count"),
+            HeaderValue::from(self.total),
+        );
+        res
+    }
+}
+
+impl<T, E> Entity for PaginatedResponse<T, E>
+where
+    T: Serialize + Entity + Send,
+    E: ResponseEntity + Send,
+{
+    fn type_name() -> Cow<'static, str> {
+        JsonResponse::<Paginated<T>, E>::type_name()
+    }
+    fn describe(comp_d: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        JsonResponse::<Paginated<T>, E>::describe(comp_d)
+    }
+}
+
+impl<T, E> ResponseEntity for PaginatedResponse<T, E>
+where
+    T: Serialize + Entity + Send,
+    E: ResponseEntity + Send,
+{
+    fn describe_responses(comp_d: &mut ComponentDescriptor) -> Responses {
+        let mut responses = JsonResponse::<Paginated<T>, E>::describe_responses(comp_d);
+        for (code, response) in responses.iter_mut() {
+            if !code.starts_with('2') {
+                continue;
+            }
+            response.headers.insert(
+                Cow::Borrowed("Link"),
+                ObjectOrReference::Object(Header {
+                    description: Some(
+                        "RFC 8288 links to the next, prev, first and last pages".into(),
+                    ),
+                    schema: Some(String::describe(comp_d)),
+                    ..Header::default()
+                }),
+            );
+            response.headers.insert(
+                Cow::Borrowed("X-Total-Count"),
+                ObjectOrReference::Object(Header {
+                    description: Some("Total number of items across all pages".into()),
+                    schema: Some(usize::describe(comp_d)),
+                    ..Header::default()
+                }),
+            );
+        }
+        responses
+    }
+}
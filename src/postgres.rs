@@ -0,0 +1,67 @@
+use bytes::BytesMut;
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+use std::error::Error;
+
+/// Implement `ToSql`/`FromSql` for a wrapper by delegating to the wrapped
+/// type, so the wrapper accepts exactly the postgres types the inner type
+/// does.
+macro_rules! delegate_sql {
+    ($T:ty, $Inner:ty) => {
+        impl ToSql for $T {
+            fn to_sql(
+                &self,
+                ty: &Type,
+                out: &mut BytesMut,
+            ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+                <$Inner as ToSql>::to_sql(&**self, ty, out)
+            }
+
+            fn accepts(ty: &Type) -> bool {
+                <$Inner as ToSql>::accepts(ty)
+            }
+
+            to_sql_checked!();
+        }
+
+        impl<'a> FromSql<'a> for $T {
+            fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+                <$Inner as FromSql>::from_sql(ty, raw).map(Self::from)
+            }
+
+            fn accepts(ty: &Type) -> bool {
+                <$Inner as FromSql>::accepts(ty)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "uuid")]
+delegate_sql!(crate::UuidWrapper, uuid::Uuid);
+#[cfg(feature = "decimal")]
+delegate_sql!(crate::DecimalWrapper, rust_decimal::Decimal);
+
+#[cfg(feature = "time")]
+delegate_sql!(crate::time_types::DateTimeType, time::OffsetDateTime);
+#[cfg(feature = "time")]
+delegate_sql!(crate::time_types::UnixSecondsType, time::OffsetDateTime);
+#[cfg(feature = "time")]
+delegate_sql!(crate::time_types::UnixMillisType, time::OffsetDateTime);
+#[cfg(feature = "time")]
+delegate_sql!(crate::time_types::Rfc2822Type, time::OffsetDateTime);
+#[cfg(feature = "time")]
+delegate_sql!(crate::time_types::DateType, time::Date);
+#[cfg(feature = "time")]
+delegate_sql!(crate::time_types::TimeType, time::Time);
+
+#[cfg(feature = "chrono")]
+delegate_sql!(
+    crate::chrono_types::DateTimeType,
+    chrono::DateTime<chrono::Utc>
+);
+#[cfg(feature = "chrono")]
+delegate_sql!(
+    crate::chrono_types::DateTimeOffsetType,
+    chrono::DateTime<chrono::FixedOffset>
+);
+#[cfg(feature = "chrono")]
+delegate_sql!(crate::chrono_types::DateType, chrono::NaiveDate);
@@ -0,0 +1,395 @@
+use rweb::{
+    filters::BoxedFilter,
+    http::StatusCode,
+    hyper::{Body, Response},
+    openapi::{
+        ComponentDescriptor, ComponentOrInlineSchema, Entity, MediaType, ResponseEntity, Responses,
+        Schema, Type,
+    },
+    reject::Reject,
+    Filter, FromRequest, Rejection, Reply,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+};
+
+use crate::{
+    schema_util::{object_properties, resolve_schema},
+    schema_validation::validate_schema,
+};
+
+/// Rejection naming the query parameter that could not be parsed.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, rweb::Schema)]
+pub struct QueryError {
+    pub parameter: String,
+    pub reason: String,
+}
+
+impl QueryError {
+    fn new(parameter: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            parameter: parameter.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid query parameter {}: {}",
+            self.parameter, self.reason
+        )
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+impl Reject for QueryError {}
+
+impl Reply for QueryError {
+    fn into_response(self) -> Response<Body> {
+        let mut res = rweb::reply::json(&self).into_response();
+        *res.status_mut() = StatusCode::BAD_REQUEST;
+        res
+    }
+}
+
+impl ResponseEntity for QueryError {
+    fn describe_responses(comp_d: &mut ComponentDescriptor) -> Responses {
+        let mut map = Responses::new();
+        map.insert(
+            Cow::Borrowed("400"),
+            rweb::openapi::Response {
+                description: Cow::Borrowed("Invalid query parameter"),
+                content: vec![(
+                    Cow::Borrowed("application/json"),
+                    MediaType {
+                        schema: Some(Self::describe(comp_d)),
+                        ..MediaType::default()
+                    },
+                )]
+                .into_iter()
+                .collect(),
+                ..rweb::openapi::Response::default()
+            },
+        );
+        map
+    }
+}
+
+fn hex_value(b: u8) -> Option<u8> {
+    (b as char).to_digit(16).map(|d| d as u8)
+}
+
+pub(crate) fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                match (hex_value(bytes[i + 1]), hex_value(bytes[i + 2])) {
+                    (Some(hi), Some(lo)) => {
+                        decoded.push(hi * 16 + lo);
+                        i += 2;
+                    }
+                    _ => decoded.push(b'%'),
+                }
+            }
+            b => decoded.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Split `a[b][]` into `["a", "b", ""]`.
+fn key_path(key: &str) -> Vec<String> {
+    match key.find('[') {
+        Some(start) if key.ends_with(']') => {
+            let mut path = vec![key[..start].to_string()];
+            path.extend(
+                key[start + 1..key.len() - 1]
+                    .split("][")
+                    .map(ToString::to_string),
+            );
+            path
+        }
+        _ => vec![key.to_string()],
+    }
+}
+
+/// Display a json pointer as the query parameter it came from.
+fn parameter_name(pointer: &str) -> String {
+    let mut name = String::new();
+    for segment in pointer.split('/').skip(1) {
+        if name.is_empty() {
+            name.push_str(segment);
+        } else if segment.parse::<usize>().is_err() {
+            name.push_str(&format!("[{}]", segment));
+        }
+    }
+    name
+}
+
+fn coerce(schema: Option<&Schema>, parameter: &str, value: &str) -> Result<Value, QueryError> {
+    let schema_type = schema.and_then(|s| s.schema_type.as_ref());
+    match schema_type {
+        Some(Type::Integer) => value
+            .parse::<i64>()
+            .map(Value::from)
+            .or_else(|_| value.parse::<u64>().map(Value::from))
+            .map_err(|_| {
+                QueryError::new(parameter, format!("expected an integer, found {:?}", value))
+            }),
+        Some(Type::Number) => value
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .ok_or_else(|| {
+                QueryError::new(parameter, format!("expected a number, found {:?}", value))
+            }),
+        Some(Type::Boolean) => match value {
+            "true" | "1" | "on" => Ok(Value::Bool(true)),
+            "false" | "0" | "off" => Ok(Value::Bool(false)),
+            _ => Err(QueryError::new(
+                parameter,
+                format!("expected a boolean, found {:?}", value),
+            )),
+        },
+        _ => Ok(Value::String(value.into())),
+    }
+}
+
+/// How array fields may be written in a query string.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ArrayFormat {
+    /// Repeated keys only, `?id=1&id=2`: a value is never split.
+    #[default]
+    Repeated,
+    /// Repeated keys or comma separated lists, `?id=1,2`: the elements of
+    /// an array can't contain a comma.
+    CommaSeparated,
+}
+
+fn property(comp_d: &ComponentDescriptor, schema: Option<&Schema>, name: &str) -> Option<Schema> {
+    let schema = schema?;
+    object_properties(comp_d, schema)
+        .into_iter()
+        .find(|(n, _)| n == name)
+        .and_then(|(_, property)| resolve_schema(comp_d, &property))
+}
+
+fn insert(
+    comp_d: &ComponentDescriptor,
+    schema: Option<&Schema>,
+    target: &mut Map<String, Value>,
+    path: &[String],
+    parameter: &str,
+    value: &str,
+    format: ArrayFormat,
+) -> Result<(), QueryError> {
+    let name = &path[0];
+    let property = property(comp_d, schema, name);
+    let is_array = property
+        .as_ref()
+        .map_or(false, |p| p.schema_type == Some(Type::Array))
+        || path.get(1).map_or(false, String::is_empty);
+    if is_array {
+        let items = property
+            .as_ref()
+            .and_then(|p| p.items.as_ref())
+            .and_then(|items| resolve_schema(comp_d, items));
+        let entry = target
+            .entry(name.clone())
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if !entry.is_array() {
+            *entry = Value::Array(vec![entry.take()]);
+        }
+        if let Value::Array(values) = entry {
+            match format {
+                ArrayFormat::Repeated => values.push(coerce(items.as_ref(), parameter, value)?),
+                ArrayFormat::CommaSeparated => {
+                    for value in value.split(',').filter(|v| !v.is_empty()) {
+                        values.push(coerce(items.as_ref(), parameter, value)?);
+                    }
+                }
+            }
+        }
+    } else if path.len() > 1 {
+        let entry = target
+            .entry(name.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+        match entry {
+            Value::Object(map) => {
+                insert(
+                    comp_d,
+                    property.as_ref(),
+                    map,
+                    &path[1..],
+                    parameter,
+                    value,
+                    format,
+                )?;
+            }
+            _ => {
+                return Err(QueryError::new(
+                    parameter,
+                    "mixes a plain value with nested parameters",
+                ))
+            }
+        }
+    } else {
+        let value = coerce(property.as_ref(), parameter, value)?;
+        match target.get_mut(name) {
+            None => {
+                target.insert(name.clone(), value);
+            }
+            Some(_) if property.is_some() => {
+                return Err(QueryError::new(parameter, "expected a single value"));
+            }
+            // undocumented parameters keep every value
+            Some(Value::Array(values)) => values.push(value),
+            Some(existing) => *existing = Value::Array(vec![existing.take(), value]),
+        }
+    }
+    Ok(())
+}
+
+/// Parse a raw query string into `T`, guided by the schema `T` documents.
+///
+/// Repeated keys (`?id=1&id=2`) fill array fields, bracketed keys
+/// (`?filter[name]=x`) fill nested objects and scalar values are coerced to
+/// the documented type before deserializing. Use [`parse_query_with`] to
+/// accept comma separated lists as well.
+///
+/// # Errors
+/// Returns the first parameter that can't be parsed, with the reason
+pub fn parse_query<T>(query: &str) -> Result<T, QueryError>
+where
+    T: DeserializeOwned + Entity,
+{
+    parse_query_with(query, ArrayFormat::default())
+}
+
+/// [`parse_query`] with array fields written as `format`.
+///
+/// # Errors
+/// Returns the first parameter that can't be parsed, with the reason
+pub fn parse_query_with<T>(query: &str, format: ArrayFormat) -> Result<T, QueryError>
+where
+    T: DeserializeOwned + Entity,
+{
+    let mut comp_d = ComponentDescriptor::new();
+    let described = T::describe(&mut comp_d);
+    let schema = resolve_schema(&comp_d, &described);
+    let mut params = Map::new();
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let mut parts = pair.splitn(2, '=');
+        let key = percent_decode(parts.next().unwrap_or_default());
+        let value = percent_decode(parts.next().unwrap_or_default());
+        let path = key_path(&key);
+        if path[0].is_empty() {
+            continue;
+        }
+        insert(
+            &comp_d,
+            schema.as_ref(),
+            &mut params,
+            &path,
+            &key,
+            &value,
+            format,
+        )?;
+    }
+    if let Some(schema) = &schema {
+        for required in &schema.required {
+            if !params.contains_key(required.as_ref()) {
+                return Err(QueryError::new(
+                    required.as_ref(),
+                    "missing required parameter",
+                ));
+            }
+        }
+    }
+    let params = Value::Object(params);
+    if let Some(violation) = validate_schema(&comp_d, &described, &params)
+        .into_iter()
+        .next()
+    {
+        return Err(QueryError::new(
+            parameter_name(&violation.path),
+            violation.message,
+        ));
+    }
+    serde_json::from_value(params).map_err(|e| QueryError::new("", e.to_string()))
+}
+
+/// Filter extracting `T` from the query string, see [`parse_query`].
+pub fn query<T>() -> impl Filter<Extract = (T,), Error = Rejection> + Clone
+where
+    T: DeserializeOwned + Entity + Send + 'static,
+{
+    query_with(ArrayFormat::default())
+}
+
+/// Filter extracting `T` from the query string, see [`parse_query_with`].
+pub fn query_with<T>(format: ArrayFormat) -> impl Filter<Extract = (T,), Error = Rejection> + Clone
+where
+    T: DeserializeOwned + Entity + Send + 'static,
+{
+    rweb::filters::query::raw()
+        .or(rweb::filters::any::any().map(String::new))
+        .unify()
+        .and_then(move |query: String| async move {
+            parse_query_with::<T>(&query, format).map_err(rweb::reject::custom)
+        })
+}
+
+/// Query string extractor backed by [`query`].
+///
+/// Use [`QueryError`] as the error type of the response to document the
+/// rejection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query<T>(pub T);
+
+impl<T> Query<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Entity for Query<T>
+where
+    T: Entity,
+{
+    fn type_name() -> Cow<'static, str> {
+        T::type_name()
+    }
+
+    fn describe(comp_d: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        T::describe(comp_d)
+    }
+}
+
+impl<T> FromRequest for Query<T>
+where
+    T: DeserializeOwned + Entity + Send + 'static,
+{
+    type Filter = BoxedFilter<(Self,)>;
+
+    fn is_query() -> bool {
+        true
+    }
+
+    fn new() -> Self::Filter {
+        query::<T>().map(Query).boxed()
+    }
+}
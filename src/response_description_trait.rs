@@ -1,3 +1,11 @@
+use rweb::{
+    http::Method,
+    openapi::{ComponentOrInlineSchema, Response, Responses, Schema, Spec},
+};
+use serde_json::Value;
+
+use crate::links::operation_method;
+
 pub trait ResponseDescriptionTrait: Send + Sync {
     fn description() -> &'static str;
 }
@@ -16,3 +24,107 @@ macro_rules! derive_response_description {
 }
 
 derive_response_description!(DefaultDescription, "");
+
+/// Set the description of the documented success responses from `D`, unless
+/// it is empty.
+pub(crate) fn describe_success<D: ResponseDescriptionTrait>(resp: &mut Responses) {
+    let description = D::description();
+    if description.is_empty() {
+        return;
+    }
+    for (code, response) in resp.iter_mut() {
+        if code.starts_with('2') {
+            response.description = description.into();
+        }
+    }
+}
+
+/// Mark the body of a documented response as deprecated and append `note`
+/// to its description.
+///
+/// A component reference is wrapped in `allOf` so the flag stays off the
+/// other uses of the component.  The operation itself is flagged by
+/// [`deprecate_operations`] once the spec is built.
+#[doc(hidden)]
+pub fn deprecate_response(response: &mut Response, note: &str) {
+    for media in response.content.values_mut() {
+        media.schema = media.schema.take().map(|schema| match schema {
+            ComponentOrInlineSchema::Inline(mut schema) => {
+                schema.deprecated = Some(true);
+                ComponentOrInlineSchema::Inline(schema)
+            }
+            component => ComponentOrInlineSchema::Inline(Schema {
+                all_of: vec![component],
+                deprecated: Some(true),
+                ..Schema::default()
+            }),
+        });
+    }
+    if note.is_empty() {
+        return;
+    }
+    response.description = if response.description.is_empty() {
+        note.to_string().into()
+    } else {
+        format!("{}\n\n{}", response.description, note).into()
+    };
+}
+
+/// Set `deprecated` on every operation of `spec` returning a
+/// `#[response(deprecated)]` type, recognised by its documented
+/// `Deprecation` header.
+///
+/// Call it on the spec built by `rweb::openapi::spec().build(...)`.
+pub fn deprecate_operations(spec: &mut Spec) {
+    update_operations(spec, |_, _, operation| {
+        operation["responses"]
+            .as_object()
+            .map_or(false, |responses| {
+                responses
+                    .values()
+                    .any(|response| response["headers"].get("Deprecation").is_some())
+            })
+    });
+}
+
+/// Set `deprecated` on the `method` operation of `path`, e.g. `/items/{id}`.
+///
+/// Returns `false` if the spec doesn't document the operation.
+pub fn deprecate_operation(spec: &mut Spec, method: &Method, path: &str) -> bool {
+    update_operations(spec, |p, m, _| p == path && m == method)
+}
+
+fn update_operations(spec: &mut Spec, select: impl Fn(&str, &Method, &Value) -> bool) -> bool {
+    let mut value = match serde_json::to_value(&*spec) {
+        Ok(value) => value,
+        Err(_) => return false,
+    };
+    let mut updated = false;
+    if let Some(paths) = value.get_mut("paths").and_then(Value::as_object_mut) {
+        for (path, item) in paths.iter_mut() {
+            if let Some(item) = item.as_object_mut() {
+                for (key, operation) in item.iter_mut() {
+                    match operation_method(key) {
+                        Some(method) if select(path, &method, operation) => {
+                            if let Some(operation) = operation.as_object_mut() {
+                                operation.insert("deprecated".into(), Value::Bool(true));
+                                updated = true;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+    if !updated {
+        return false;
+    }
+    match serde_json::from_value(value) {
+        Ok(value) => {
+            *spec = value;
+            true
+        }
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,68 @@
+use rweb::openapi::{ComponentDescriptor, ComponentOrInlineSchema};
+use serde_json::Value;
+
+use crate::schema_util::{object_properties, resolve_schema};
+
+/// A serialized field of a struct deriving `RwebSchemaAs`.
+pub enum SourceField {
+    Named(&'static str, ComponentOrInlineSchema),
+    /// `#[serde(flatten)]`: the properties of the schema are merged in.
+    Flattened(ComponentOrInlineSchema),
+}
+
+/// Check that the serialized fields of a struct are exactly the properties
+/// of `target`, with the same schemas.
+///
+/// Both sides are compared by serialized name: the properties of `target`
+/// already reflect its serde renames and skipped fields.
+///
+/// # Errors
+/// Returns a description of the first mismatch
+pub fn check_fields(
+    comp_d: &ComponentDescriptor,
+    target: &ComponentOrInlineSchema,
+    fields: Vec<SourceField>,
+) -> Result<(), String> {
+    let mut source = Vec::new();
+    for field in fields {
+        match field {
+            SourceField::Named(name, schema) => source.push((name.to_string(), schema)),
+            SourceField::Flattened(schema) => {
+                let schema = resolve_schema(comp_d, &schema)
+                    .ok_or_else(|| "flattened field has no schema".to_string())?;
+                source.extend(object_properties(comp_d, &schema));
+            }
+        }
+    }
+    let target =
+        resolve_schema(comp_d, target).ok_or_else(|| "target has no schema".to_string())?;
+    let target = object_properties(comp_d, &target);
+    for (name, schema) in &source {
+        match target.iter().find(|(property, _)| property == name) {
+            Some((_, expected)) => {
+                if shape(comp_d, schema) != shape(comp_d, expected) {
+                    return Err(format!("field {} has a different schema", name));
+                }
+            }
+            None => return Err(format!("field {} is not a property of the target", name)),
+        }
+    }
+    if let Some((property, _)) = target
+        .iter()
+        .find(|(property, _)| source.iter().all(|(name, _)| name != property))
+    {
+        return Err(format!("property {} is missing", property));
+    }
+    Ok(())
+}
+
+/// The resolved schema without the documentation only members.
+fn shape(comp_d: &ComponentDescriptor, schema: &ComponentOrInlineSchema) -> Option<Value> {
+    let mut value = serde_json::to_value(resolve_schema(comp_d, schema)?).ok()?;
+    if let Value::Object(map) = &mut value {
+        for key in &["description", "example", "title"] {
+            map.remove(*key);
+        }
+    }
+    Some(value)
+}
@@ -0,0 +1,31 @@
+use rweb::openapi::{ComponentDescriptor, ComponentOrInlineSchema, Schema};
+
+/// Look up the schema behind a component reference, or clone an inline one.
+pub(crate) fn resolve_schema(
+    comp_d: &ComponentDescriptor,
+    schema: &ComponentOrInlineSchema,
+) -> Option<Schema> {
+    match schema {
+        ComponentOrInlineSchema::Inline(schema) => Some(schema.clone()),
+        ComponentOrInlineSchema::Component { name } => comp_d.get_component(name).cloned(),
+    }
+}
+
+/// Properties of an object schema, including those merged in through
+/// `allOf` (which is how flattened fields are described).
+pub(crate) fn object_properties(
+    comp_d: &ComponentDescriptor,
+    schema: &Schema,
+) -> Vec<(String, ComponentOrInlineSchema)> {
+    let mut properties: Vec<_> = schema
+        .properties
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect();
+    for sub in &schema.all_of {
+        if let Some(sub) = resolve_schema(comp_d, sub) {
+            properties.extend(object_properties(comp_d, &sub));
+        }
+    }
+    properties
+}
@@ -0,0 +1,533 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+use rweb::openapi::{ComponentDescriptor, ComponentOrInlineSchema, Entity, Schema, Type};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+#[cfg(feature = "decimal")]
+use std::str::FromStr;
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    sync::RwLock,
+};
+#[cfg(feature = "uuid")]
+use uuid::Uuid;
+
+use crate::{
+    schema_util::{object_properties, resolve_schema},
+    validation::{is_email, is_url},
+};
+
+const MAX_DEPTH: usize = 32;
+
+/// Compiled `pattern`s of the schemas validated so far, with the error for
+/// the ones that don't compile.
+static PATTERNS: Lazy<RwLock<HashMap<String, Result<Regex, String>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+fn compiled_pattern(pattern: &str) -> Result<Regex, String> {
+    if let Some(compiled) = PATTERNS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(pattern)
+    {
+        return compiled.clone();
+    }
+    let compiled = Regex::new(pattern).map_err(|e| e.to_string());
+    PATTERNS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(pattern.to_string(), compiled.clone());
+    compiled
+}
+
+/// A single place where a json value disagrees with a schema.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, rweb::Schema)]
+pub struct Violation {
+    #[schema(description = "JSON pointer to the offending value")]
+    pub path: String,
+    pub message: String,
+}
+
+impl Violation {
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(f, "{}: {}", path, self.message)
+    }
+}
+
+/// Validate `value` against the schema `T` documents.
+#[must_use]
+pub fn validate<T: Entity>(value: &Value) -> Vec<Violation> {
+    let mut comp_d = ComponentDescriptor::new();
+    let schema = T::describe(&mut comp_d);
+    validate_schema(&comp_d, &schema, value)
+}
+
+/// Validate `value` against `schema`, resolving components through `comp_d`.
+///
+/// Object members the schema doesn't declare are allowed, as in OpenAPI
+/// without `additionalProperties`, which rweb schemas can't set to `false`.
+#[must_use]
+pub fn validate_schema(
+    comp_d: &ComponentDescriptor,
+    schema: &ComponentOrInlineSchema,
+    value: &Value,
+) -> Vec<Violation> {
+    validate_with(comp_d, schema, value, Undeclared::Allow)
+}
+
+/// How object members missing from the `properties` of a schema without
+/// `additionalProperties` are treated.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Undeclared {
+    Allow,
+    Deny,
+}
+
+fn validate_with(
+    comp_d: &ComponentDescriptor,
+    schema: &ComponentOrInlineSchema,
+    value: &Value,
+    undeclared: Undeclared,
+) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    if let Some(schema) = resolve_schema(comp_d, schema) {
+        check(comp_d, &schema, value, "", 0, undeclared, &mut violations);
+    }
+    violations
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn schema_type_name(schema_type: &Type) -> &'static str {
+    match schema_type {
+        Type::Object => "object",
+        Type::Array => "array",
+        Type::String => "string",
+        Type::Integer => "integer",
+        Type::Number => "number",
+        Type::Boolean => "boolean",
+    }
+}
+
+fn matches_type(schema_type: &Type, value: &Value) -> bool {
+    match schema_type {
+        Type::Object => value.is_object(),
+        Type::Array => value.is_array(),
+        Type::String => value.is_string(),
+        Type::Integer => value.is_i64() || value.is_u64(),
+        Type::Number => value.is_number(),
+        Type::Boolean => value.is_boolean(),
+    }
+}
+
+fn check(
+    comp_d: &ComponentDescriptor,
+    schema: &Schema,
+    value: &Value,
+    path: &str,
+    depth: usize,
+    undeclared: Undeclared,
+    violations: &mut Vec<Violation>,
+) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    if let Some(schema_type) = &schema.schema_type {
+        if !matches_type(schema_type, value) {
+            if !(value.is_null() && schema.nullable == Some(true)) {
+                violations.push(Violation::new(
+                    path,
+                    format!(
+                        "expected {}, found {} {}",
+                        schema_type_name(schema_type),
+                        type_name(value),
+                        value
+                    ),
+                ));
+            }
+            return;
+        }
+    }
+    if !schema.enum_values.is_empty() {
+        let allowed = value
+            .as_str()
+            .map_or(false, |s| schema.enum_values.iter().any(|e| e == s));
+        if !allowed {
+            violations.push(Violation::new(
+                path,
+                format!(
+                    "expected one of {}, found {}",
+                    schema.enum_values.join(", "),
+                    value
+                ),
+            ));
+        }
+    }
+    match value {
+        Value::String(s) => {
+            if let Err(message) = check_format(&schema.format, s) {
+                violations.push(Violation::new(path, message));
+            }
+            let length = s.chars().count();
+            if let Some(min_length) = schema.min_length.map(|m| m as usize) {
+                if length < min_length {
+                    violations.push(Violation::new(
+                        path,
+                        format!("length {} is less than {}", length, min_length),
+                    ));
+                }
+            }
+            if let Some(max_length) = schema.max_length.map(|m| m as usize) {
+                if length > max_length {
+                    violations.push(Violation::new(
+                        path,
+                        format!("length {} is greater than {}", length, max_length),
+                    ));
+                }
+            }
+            if let Some(pattern) = &schema.pattern {
+                match compiled_pattern(pattern) {
+                    Ok(regex) => {
+                        if !regex.is_match(s) {
+                            violations.push(Violation::new(
+                                path,
+                                format!("{:?} does not match {}", s, pattern),
+                            ));
+                        }
+                    }
+                    Err(e) => violations.push(Violation::new(
+                        path,
+                        format!("invalid pattern {}: {}", pattern, e),
+                    )),
+                }
+            }
+        }
+        Value::Number(n) => {
+            let n = n.as_f64().unwrap_or_default();
+            if let Some(minimum) = schema.minimum.as_ref().and_then(|m| m.as_f64()) {
+                if n < minimum {
+                    violations.push(Violation::new(
+                        path,
+                        format!("{} is less than the minimum {}", n, minimum),
+                    ));
+                }
+            }
+            if let Some(maximum) = schema.maximum.as_ref().and_then(|m| m.as_f64()) {
+                if n > maximum {
+                    violations.push(Violation::new(
+                        path,
+                        format!("{} is greater than the maximum {}", n, maximum),
+                    ));
+                }
+            }
+            // Checked in `f64`, so `multipleOf` values that aren't exactly
+            // representable (e.g. `0.01`) only match within a tolerance.
+            if let Some(multiple_of) = schema.multiple_of.as_ref().and_then(|m| m.as_f64()) {
+                let quotient = n / multiple_of;
+                if multiple_of > 0.0 && (quotient - quotient.round()).abs() > 1e-9 {
+                    violations.push(Violation::new(
+                        path,
+                        format!("{} is not a multiple of {}", n, multiple_of),
+                    ));
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema
+                .items
+                .as_ref()
+                .and_then(|items| resolve_schema(comp_d, items))
+            {
+                for (i, item) in items.iter().enumerate() {
+                    let path = format!("{}/{}", path, i);
+                    check(
+                        comp_d,
+                        &item_schema,
+                        item,
+                        &path,
+                        depth + 1,
+                        undeclared,
+                        violations,
+                    );
+                }
+            }
+        }
+        Value::Object(map) => {
+            let properties = object_properties(comp_d, schema);
+            let mut required: Vec<String> = schema.required.iter().map(|r| r.to_string()).collect();
+            for sub in &schema.all_of {
+                if let Some(sub) = resolve_schema(comp_d, sub) {
+                    for r in &sub.required {
+                        if !required.iter().any(|name| name == r) {
+                            required.push(r.to_string());
+                        }
+                    }
+                }
+            }
+            for name in &required {
+                if !map.contains_key(name) {
+                    violations.push(Violation::new(
+                        path,
+                        format!("missing required field {}", name),
+                    ));
+                }
+            }
+            let additional = schema
+                .additional_properties
+                .as_ref()
+                .and_then(|additional| resolve_schema(comp_d, additional));
+            for (key, item) in map {
+                let item_path = format!("{}/{}", path, escape_pointer(key));
+                if let Some((_, property)) = properties.iter().find(|(name, _)| name == key) {
+                    if item.is_null() && !required.contains(key) {
+                        continue;
+                    }
+                    if let Some(property) = resolve_schema(comp_d, property) {
+                        check(
+                            comp_d,
+                            &property,
+                            item,
+                            &item_path,
+                            depth + 1,
+                            undeclared,
+                            violations,
+                        );
+                    }
+                } else if let Some(additional) = &additional {
+                    check(
+                        comp_d,
+                        additional,
+                        item,
+                        &item_path,
+                        depth + 1,
+                        undeclared,
+                        violations,
+                    );
+                } else if undeclared == Undeclared::Deny && !properties.is_empty() {
+                    violations.push(Violation::new(&item_path, "not declared in the schema"));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn check_format(format: &str, s: &str) -> Result<(), String> {
+    let valid = match format {
+        "uuid" => is_uuid(s),
+        "decimal" => is_decimal(s),
+        "date" => is_date(s),
+        "date-time" => is_date_time(s),
+        "email" => is_email(s),
+        "uri" => is_url(s),
+        _ => true,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("{:?} is not a valid {}", s, format))
+    }
+}
+
+#[cfg(feature = "uuid")]
+fn is_uuid(s: &str) -> bool {
+    Uuid::parse_str(s).is_ok()
+}
+
+/// `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`
+#[cfg(not(feature = "uuid"))]
+fn is_uuid(s: &str) -> bool {
+    s.len() == 36
+        && s.bytes().enumerate().all(|(i, b)| match i {
+            8 | 13 | 18 | 23 => b == b'-',
+            _ => b.is_ascii_hexdigit(),
+        })
+}
+
+#[cfg(feature = "decimal")]
+fn is_decimal(s: &str) -> bool {
+    Decimal::from_str(s).is_ok()
+}
+
+/// `[-+]digits[.digits]`
+#[cfg(not(feature = "decimal"))]
+fn is_decimal(s: &str) -> bool {
+    let s = s.strip_prefix(|c| c == '-' || c == '+').unwrap_or(s);
+    let mut parts = s.splitn(2, '.');
+    let whole = parts.next().unwrap_or_default();
+    let fraction = parts.next().unwrap_or_default();
+    !(whole.is_empty() && fraction.is_empty())
+        && whole.bytes().all(|b| b.is_ascii_digit())
+        && fraction.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn digits(s: &str, min: u32, max: u32) -> bool {
+    s.bytes().all(|b| b.is_ascii_digit())
+        && s.parse::<u32>().map_or(false, |n| n >= min && n <= max)
+}
+
+/// `YYYY-MM-DD`
+///
+/// The date functions slice at fixed byte offsets, so anything that isn't
+/// ascii is rejected up front.
+fn is_date(s: &str) -> bool {
+    s.len() == 10
+        && s.is_ascii()
+        && &s[4..5] == "-"
+        && &s[7..8] == "-"
+        && digits(&s[..4], 0, 9999)
+        && digits(&s[5..7], 1, 12)
+        && digits(&s[8..], 1, 31)
+}
+
+/// RFC 3339 `date-time`: `YYYY-MM-DDTHH:MM:SS[.frac](Z|+HH:MM)`
+fn is_date_time(s: &str) -> bool {
+    if s.len() < 20 || !s.is_ascii() {
+        return false;
+    }
+    let (date, rest) = s.split_at(10);
+    if !is_date(date) || !(rest.starts_with('T') || rest.starts_with('t')) {
+        return false;
+    }
+    let rest = &rest[1..];
+    if rest.len() < 9 {
+        return false;
+    }
+    let (time, rest) = rest.split_at(8);
+    let time_valid = &time[2..3] == ":"
+        && &time[5..6] == ":"
+        && digits(&time[..2], 0, 23)
+        && digits(&time[3..5], 0, 59)
+        && digits(&time[6..], 0, 60);
+    if !time_valid {
+        return false;
+    }
+    let offset = match rest.strip_prefix('.') {
+        Some(frac) => {
+            let end = frac
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(frac.len());
+            if end == 0 {
+                return false;
+            }
+            &frac[end..]
+        }
+        None => rest,
+    };
+    match offset {
+        "Z" | "z" => true,
+        offset if offset.len() == 6 => {
+            (offset.starts_with('+') || offset.starts_with('-'))
+                && &offset[3..4] == ":"
+                && digits(&offset[1..3], 0, 23)
+                && digits(&offset[4..], 0, 59)
+        }
+        _ => false,
+    }
+}
+
+/// Serialized sample that disagrees with its documented schema.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SchemaMismatch {
+    pub type_name: String,
+    pub value: Value,
+    pub violations: Vec<Violation>,
+}
+
+impl Display for SchemaMismatch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "serialized {} does not match its schema:",
+            self.type_name
+        )?;
+        for violation in &self.violations {
+            writeln!(f, "  {}", violation)?;
+        }
+        write!(f, "serialized value: {}", self.value)
+    }
+}
+
+impl std::error::Error for SchemaMismatch {}
+
+/// Serialize `sample` and validate the json against `T::describe()`.
+///
+/// Unlike [`validate`], members of an object the schema doesn't declare are
+/// reported: the sample should serialize exactly what is documented.
+///
+/// # Errors
+/// Returns every violation found, each with the json pointer it occurs at,
+/// or a single violation at the root when `sample` fails to serialize
+pub fn check_sample<T>(sample: &T) -> Result<(), SchemaMismatch>
+where
+    T: Serialize + Entity,
+{
+    let value = match serde_json::to_value(sample) {
+        Ok(value) => value,
+        Err(e) => {
+            return Err(SchemaMismatch {
+                type_name: T::type_name().into(),
+                value: Value::Null,
+                violations: vec![Violation::new("", format!("failed to serialize: {}", e))],
+            })
+        }
+    };
+    let mut comp_d = ComponentDescriptor::new();
+    let schema = T::describe(&mut comp_d);
+    let violations = validate_with(&comp_d, &schema, &value, Undeclared::Deny);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(SchemaMismatch {
+            type_name: T::type_name().into(),
+            value,
+            violations,
+        })
+    }
+}
+
+/// Test helper asserting that the serde output of `sample` matches the
+/// documented schema of `T`.
+///
+/// # Panics
+/// Panics with a per path list of differences
+#[track_caller]
+pub fn assert_matches_schema<T>(sample: &T)
+where
+    T: Serialize + Entity,
+{
+    if let Err(e) = check_sample(sample) {
+        panic!("{}", e);
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(feature = "sqlx-postgres")]
+use sqlx::postgres::Postgres;
+#[cfg(feature = "sqlx-sqlite")]
+use sqlx::sqlite::Sqlite;
+use sqlx::{encode::IsNull, error::BoxDynError, Database, Decode, Encode, Type};
+
+/// Implement `Type`/`Encode`/`Decode` for a wrapper on `$DB` by delegating to
+/// the wrapped type.
+macro_rules! delegate_sqlx {
+    ($T:ty, $Inner:ty, $DB:ty) => {
+        impl Type<$DB> for $T {
+            fn type_info() -> <$DB as Database>::TypeInfo {
+                <$Inner as Type<$DB>>::type_info()
+            }
+
+            fn compatible(ty: &<$DB as Database>::TypeInfo) -> bool {
+                <$Inner as Type<$DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q> Encode<'q, $DB> for $T {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <$DB as Database>::ArgumentBuffer<'q>,
+            ) -> Result<IsNull, BoxDynError> {
+                <$Inner as Encode<'q, $DB>>::encode_by_ref(&**self, buf)
+            }
+
+            fn size_hint(&self) -> usize {
+                <$Inner as Encode<'q, $DB>>::size_hint(&**self)
+            }
+        }
+
+        impl<'r> Decode<'r, $DB> for $T {
+            fn decode(value: <$DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+                <$Inner as Decode<'r, $DB>>::decode(value).map(Self::from)
+            }
+        }
+    };
+    ($T:ty, $Inner:ty) => {
+        #[cfg(feature = "sqlx-postgres")]
+        delegate_sqlx!($T, $Inner, Postgres);
+        #[cfg(feature = "sqlx-sqlite")]
+        delegate_sqlx!($T, $Inner, Sqlite);
+    };
+}
+
+#[cfg(feature = "uuid")]
+delegate_sqlx!(crate::UuidWrapper, uuid::Uuid);
+
+#[cfg(all(feature = "decimal", feature = "sqlx-postgres"))]
+delegate_sqlx!(crate::DecimalWrapper, rust_decimal::Decimal, Postgres);
+
+// sqlx has no sqlite support for `Decimal`, store it as text instead
+#[cfg(all(feature = "decimal", feature = "sqlx-sqlite"))]
+impl Type<Sqlite> for crate::DecimalWrapper {
+    fn type_info() -> <Sqlite as Database>::TypeInfo {
+        <String as Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &<Sqlite as Database>::TypeInfo) -> bool {
+        <String as Type<Sqlite>>::compatible(ty)
+            || <i64 as Type<Sqlite>>::compatible(ty)
+            || <f64 as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+#[cfg(all(feature = "decimal", feature = "sqlx-sqlite"))]
+impl<'q> Encode<'q, Sqlite> for crate::DecimalWrapper {
+    fn encode_by_ref(
+        &self,
+        buf: &mut <Sqlite as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        <String as Encode<'q, Sqlite>>::encode(self.to_string(), buf)
+    }
+}
+
+#[cfg(all(feature = "decimal", feature = "sqlx-sqlite"))]
+impl<'r> Decode<'r, Sqlite> for crate::DecimalWrapper {
+    fn decode(value: <Sqlite as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(<&str as Decode<'r, Sqlite>>::decode(value)?.parse()?)
+    }
+}
+
+#[cfg(feature = "time")]
+delegate_sqlx!(crate::time_types::DateTimeType, time::OffsetDateTime);
+#[cfg(feature = "time")]
+delegate_sqlx!(crate::time_types::DateType, time::Date);
+
+#[cfg(feature = "chrono")]
+delegate_sqlx!(
+    crate::chrono_types::DateTimeType,
+    chrono::DateTime<chrono::Utc>
+);
+#[cfg(feature = "chrono")]
+delegate_sqlx!(
+    crate::chrono_types::DateTimeOffsetType,
+    chrono::DateTime<chrono::FixedOffset>
+);
+#[cfg(feature = "chrono")]
+delegate_sqlx!(crate::chrono_types::DateType, chrono::NaiveDate);
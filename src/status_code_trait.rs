@@ -30,8 +30,15 @@ impl StatusCodeTrait for StatusCodeNoContent {
 
 pub struct StatusCodeValue<const S: u16> {}
 
+impl<const S: u16> StatusCodeValue<S> {
+    /// Fails to compile for any `S` that `StatusCode::from_u16` would reject.
+    pub const VALID: () = assert!(S >= 100 && S < 1000, "invalid http status code");
+}
+
 impl<const S: u16> StatusCodeTrait for StatusCodeValue<S> {
     fn status_code() -> StatusCode {
-        StatusCode::from_u16(S).unwrap_or(StatusCode::OK)
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID;
+        StatusCode::from_u16(S).expect("status code checked at compile time")
     }
 }
@@ -0,0 +1,677 @@
+use derive_more::{Deref, From, Into};
+use rweb::openapi::{ComponentDescriptor, ComponentOrInlineSchema, Entity, Schema, Type};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    borrow::Cow,
+    convert::TryFrom,
+    fmt::{self, Formatter},
+    num::ParseIntError,
+    str::FromStr,
+};
+use time::{
+    format_description::{
+        well_known::{Rfc2822, Rfc3339},
+        FormatItem,
+    },
+    macros::format_description,
+    Date, Duration, OffsetDateTime, Time, UtcOffset,
+};
+#[cfg(feature = "time-tz")]
+use time_tz::{TimeZone, Tz};
+
+use crate::normalize_rfc3339;
+
+/// Timestamp serialized as RFC 3339, e.g. `2023-01-01T00:00:00Z`.
+#[derive(Into, From, Deref, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DateTimeType(OffsetDateTime);
+
+impl Entity for DateTimeType {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("date-time")
+    }
+
+    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Inline(Schema {
+            schema_type: Some(Type::String),
+            format: Self::type_name(),
+            example: Some(serde_json::value::Value::String(
+                "2023-01-01T00:00:00Z".into(),
+            )),
+            ..Schema::default()
+        })
+    }
+}
+
+impl FromStr for DateTimeType {
+    type Err = time::error::Parse;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        OffsetDateTime::parse(&normalize_rfc3339(s), &Rfc3339).map(Self)
+    }
+}
+
+impl fmt::Display for DateTimeType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.0.format(&Rfc3339) {
+            Ok(s) => f.write_str(&s),
+            // years outside 0000..=9999 can't be written as RFC 3339
+            Err(_) => write!(f, "{}", self.0),
+        }
+    }
+}
+
+/// The format `serde-human-readable` used to write `DateTimeType`, still
+/// accepted when deserializing.
+const HUMAN_READABLE_FORMAT: &[FormatItem<'static>] = format_description!(
+    "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond] \
+     [offset_hour sign:mandatory]:[offset_minute]:[offset_second]"
+);
+
+impl Serialize for DateTimeType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DateTimeType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .or_else(|e| {
+                OffsetDateTime::parse(&s, HUMAN_READABLE_FORMAT)
+                    .map(Self)
+                    .map_err(|_| e)
+            })
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Error parsing one of the epoch timestamp wrappers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimestampError {
+    Int(ParseIntError),
+    Range(time::error::ComponentRange),
+}
+
+impl fmt::Display for TimestampError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Int(e) => write!(f, "{}", e),
+            Self::Range(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for TimestampError {}
+
+/// Timestamp serialized as integer seconds since the Unix epoch.
+#[derive(Into, From, Deref, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UnixSecondsType(OffsetDateTime);
+
+impl UnixSecondsType {
+    /// # Errors
+    /// Returns an error if `seconds` is outside the supported range
+    pub fn from_seconds(seconds: i64) -> Result<Self, TimestampError> {
+        OffsetDateTime::from_unix_timestamp(seconds)
+            .map(Self)
+            .map_err(TimestampError::Range)
+    }
+
+    #[must_use]
+    pub fn seconds(&self) -> i64 {
+        self.0.unix_timestamp()
+    }
+}
+
+/// Timestamp serialized as integer milliseconds since the Unix epoch.
+#[derive(Into, From, Deref, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UnixMillisType(OffsetDateTime);
+
+impl UnixMillisType {
+    /// # Errors
+    /// Returns an error if `millis` is outside the supported range
+    pub fn from_millis(millis: i64) -> Result<Self, TimestampError> {
+        OffsetDateTime::from_unix_timestamp_nanos(i128::from(millis) * 1_000_000)
+            .map(Self)
+            .map_err(TimestampError::Range)
+    }
+
+    #[must_use]
+    pub fn millis(&self) -> i64 {
+        (self.0.unix_timestamp_nanos() / 1_000_000) as i64
+    }
+}
+
+macro_rules! epoch_type {
+    ($T:ident, $from:ident, $to:ident, $name:expr, $description:expr) => {
+        impl FromStr for $T {
+            type Err = TimestampError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let n = s.trim().parse().map_err(TimestampError::Int)?;
+                Self::$from(n)
+            }
+        }
+
+        impl fmt::Display for $T {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                write!(f, "{}", self.$to())
+            }
+        }
+
+        impl Serialize for $T {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_i64(self.$to())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $T {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let n = i64::deserialize(deserializer)?;
+                Self::$from(n).map_err(serde::de::Error::custom)
+            }
+        }
+
+        impl Entity for $T {
+            fn type_name() -> Cow<'static, str> {
+                Cow::Borrowed($name)
+            }
+
+            fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+                ComponentOrInlineSchema::Inline(Schema {
+                    description: Cow::Borrowed($description),
+                    schema_type: Some(Type::Integer),
+                    format: Cow::Borrowed("int64"),
+                    ..Schema::default()
+                })
+            }
+        }
+    };
+}
+
+epoch_type!(
+    UnixSecondsType,
+    from_seconds,
+    seconds,
+    "unix-seconds",
+    "Seconds since the Unix epoch"
+);
+epoch_type!(
+    UnixMillisType,
+    from_millis,
+    millis,
+    "unix-millis",
+    "Milliseconds since the Unix epoch"
+);
+
+/// Timestamp serialized as RFC 2822, e.g. `Sun, 01 Jan 2023 00:00:00 +0000`.
+#[derive(Into, From, Deref, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rfc2822Type(OffsetDateTime);
+
+impl FromStr for Rfc2822Type {
+    type Err = time::error::Parse;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        OffsetDateTime::parse(s.trim(), &Rfc2822).map(Self)
+    }
+}
+
+impl fmt::Display for Rfc2822Type {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.0.format(&Rfc2822) {
+            Ok(s) => f.write_str(&s),
+            Err(_) => write!(f, "{}", self.0),
+        }
+    }
+}
+
+string_serde!(Rfc2822Type);
+
+impl Entity for Rfc2822Type {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("rfc2822")
+    }
+
+    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Inline(Schema {
+            description: Cow::Borrowed("RFC 2822 date-time"),
+            schema_type: Some(Type::String),
+            format: Self::type_name(),
+            example: Some(serde_json::value::Value::String(
+                "Sun, 01 Jan 2023 00:00:00 +0000".into(),
+            )),
+            ..Schema::default()
+        })
+    }
+}
+
+#[derive(Into, From, Serialize, Deserialize, Deref, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct DateType(Date);
+
+const DATE_FORMAT: &[FormatItem<'static>] = format_description!("[year]-[month]-[day]");
+const DATE_FORMAT_BASIC: &[FormatItem<'static>] = format_description!("[year][month][day]");
+
+impl FromStr for DateType {
+    type Err = time::error::Parse;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Date::parse(s, DATE_FORMAT)
+            .or_else(|e| Date::parse(s, DATE_FORMAT_BASIC).map_err(|_| e))
+            .map(Self)
+    }
+}
+
+impl fmt::Display for DateType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.0.format(DATE_FORMAT) {
+            Ok(s) => f.write_str(&s),
+            Err(_) => write!(f, "{}", self.0),
+        }
+    }
+}
+
+impl Entity for DateType {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("date")
+    }
+
+    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Inline(Schema {
+            schema_type: Some(Type::String),
+            format: Self::type_name(),
+            example: Some(serde_json::value::Value::String("2023-01-01".into())),
+            ..Schema::default()
+        })
+    }
+}
+
+/// Time of day serialized as `HH:MM:SS[.fraction]`.
+#[derive(Into, From, Deref, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TimeType(Time);
+
+const TIME_FORMAT: &[FormatItem<'static>] = format_description!("[hour]:[minute]:[second]");
+const TIME_FORMAT_SUBSECOND: &[FormatItem<'static>] =
+    format_description!("[hour]:[minute]:[second].[subsecond]");
+const TIME_FORMAT_MINUTES: &[FormatItem<'static>] = format_description!("[hour]:[minute]");
+
+impl FromStr for TimeType {
+    type Err = time::error::Parse;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Time::parse(s, TIME_FORMAT)
+            .or_else(|e| Time::parse(s, TIME_FORMAT_SUBSECOND).map_err(|_| e))
+            .or_else(|e| Time::parse(s, TIME_FORMAT_MINUTES).map_err(|_| e))
+            .map(Self)
+    }
+}
+
+impl fmt::Display for TimeType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let format = if self.0.nanosecond() == 0 {
+            TIME_FORMAT
+        } else {
+            TIME_FORMAT_SUBSECOND
+        };
+        match self.0.format(format) {
+            Ok(s) => f.write_str(&s),
+            Err(_) => write!(f, "{}", self.0),
+        }
+    }
+}
+
+string_serde!(TimeType);
+
+impl Entity for TimeType {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("time")
+    }
+
+    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Inline(Schema {
+            schema_type: Some(Type::String),
+            format: Self::type_name(),
+            example: Some(serde_json::value::Value::String("13:45:00".into())),
+            ..Schema::default()
+        })
+    }
+}
+
+/// Error parsing a [`DurationType`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DurationError(String);
+
+impl fmt::Display for DurationError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid duration {:?}, expected ISO 8601 (PT1H30M) or seconds",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for DurationError {}
+
+/// Duration serialized as ISO 8601, e.g. `PT1H30M`.
+///
+/// Numeric seconds (`5400` or `"5400"`) are accepted when parsing. Years
+/// and months are rejected since their length is ambiguous.
+#[derive(Into, From, Deref, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DurationType(Duration);
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+fn seconds_to_duration(seconds: f64) -> Option<Duration> {
+    if seconds.is_finite() && seconds.abs() < i64::MAX as f64 {
+        Some(Duration::seconds_f64(seconds))
+    } else {
+        None
+    }
+}
+
+fn nanos_to_duration(nanos: i128) -> Option<Duration> {
+    let seconds = i64::try_from(nanos / NANOS_PER_SECOND).ok()?;
+    Some(Duration::new(seconds, (nanos % NANOS_PER_SECOND) as i32))
+}
+
+/// Parse an unsigned decimal count of `unit_nanos` into nanoseconds without
+/// going through `f64`, so long durations keep their fractional part.
+fn decimal_nanos(number: &str, unit_nanos: i128) -> Option<i128> {
+    let mut parts = number.splitn(2, |c| c == '.' || c == ',');
+    let whole = parts.next().unwrap_or_default();
+    let fraction = parts.next().unwrap_or_default();
+    let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty()) || !digits(whole) || !digits(fraction) {
+        return None;
+    }
+    let whole: i128 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    // digits past 10^-18 of a week are well below a nanosecond
+    let fraction = &fraction[..fraction.len().min(18)];
+    let fraction_nanos = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<i128>().ok()? * unit_nanos / 10_i128.pow(fraction.len() as u32)
+    };
+    whole.checked_mul(unit_nanos)?.checked_add(fraction_nanos)
+}
+
+fn split_sign(s: &str) -> (bool, &str) {
+    match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    }
+}
+
+fn parse_seconds(s: &str) -> Option<Duration> {
+    let (negative, s) = split_sign(s);
+    let nanos = decimal_nanos(s, NANOS_PER_SECOND)?;
+    nanos_to_duration(if negative { -nanos } else { nanos })
+}
+
+fn parse_iso_duration(s: &str) -> Option<Duration> {
+    let (negative, s) = split_sign(s);
+    let s = s.strip_prefix('P').or_else(|| s.strip_prefix('p'))?;
+    let (date, time) = match s.find(|c| c == 'T' || c == 't') {
+        Some(i) if i + 1 < s.len() => (&s[..i], Some(&s[i + 1..])),
+        Some(_) => return None,
+        None => (s, None),
+    };
+    let mut nanos: i128 = 0;
+    let mut components = 0;
+    let mut parse_components = |part: &str, units: &[(char, i128)]| -> Option<()> {
+        let mut number = String::new();
+        let mut next_unit = 0;
+        for c in part.chars() {
+            if c.is_ascii_digit() || c == '.' || c == ',' {
+                number.push(c);
+            } else {
+                let c = c.to_ascii_uppercase();
+                let offset = units[next_unit..].iter().position(|(u, _)| *u == c)?;
+                let value = decimal_nanos(&number, units[next_unit + offset].1)?;
+                nanos = nanos.checked_add(value)?;
+                next_unit += offset + 1;
+                components += 1;
+                number.clear();
+            }
+        }
+        if number.is_empty() {
+            Some(())
+        } else {
+            None
+        }
+    };
+    parse_components(
+        date,
+        &[
+            ('W', 604_800 * NANOS_PER_SECOND),
+            ('D', 86_400 * NANOS_PER_SECOND),
+        ],
+    )?;
+    if let Some(time) = time {
+        parse_components(
+            time,
+            &[
+                ('H', 3_600 * NANOS_PER_SECOND),
+                ('M', 60 * NANOS_PER_SECOND),
+                ('S', NANOS_PER_SECOND),
+            ],
+        )?;
+    }
+    if components == 0 {
+        return None;
+    }
+    nanos_to_duration(if negative { -nanos } else { nanos })
+}
+
+impl FromStr for DurationType {
+    type Err = DurationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        parse_iso_duration(s)
+            .or_else(|| parse_seconds(s))
+            .map(Self)
+            .ok_or_else(|| DurationError(s.into()))
+    }
+}
+
+impl fmt::Display for DurationType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let nanos = self.0.whole_nanoseconds();
+        if nanos < 0 {
+            f.write_str("-")?;
+        }
+        let nanos = nanos.unsigned_abs();
+        let seconds = nanos / 1_000_000_000;
+        let fraction = nanos % 1_000_000_000;
+        let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+        f.write_str("PT")?;
+        if hours > 0 {
+            write!(f, "{}H", hours)?;
+        }
+        if minutes > 0 {
+            write!(f, "{}M", minutes)?;
+        }
+        if fraction > 0 {
+            let fraction = format!("{:09}", fraction);
+            write!(f, "{}.{}S", seconds, fraction.trim_end_matches('0'))
+        } else if seconds > 0 || (hours == 0 && minutes == 0) {
+            write!(f, "{}S", seconds)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Serialize for DurationType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DurationType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Whole(i64),
+            Seconds(f64),
+            Text(String),
+        }
+        match Repr::deserialize(deserializer)? {
+            Repr::Whole(seconds) => Ok(Self(Duration::seconds(seconds))),
+            Repr::Seconds(seconds) => seconds_to_duration(seconds)
+                .map(Self)
+                .ok_or_else(|| serde::de::Error::custom(DurationError(seconds.to_string()))),
+            Repr::Text(s) => s.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+impl Entity for DurationType {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("duration")
+    }
+
+    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Inline(Schema {
+            description: Cow::Borrowed("ISO 8601 duration, numeric seconds are also accepted"),
+            schema_type: Some(Type::String),
+            format: Self::type_name(),
+            example: Some(serde_json::value::Value::String("PT1H30M".into())),
+            ..Schema::default()
+        })
+    }
+}
+
+/// Offset from UTC serialized as `+HH:MM`.
+#[derive(Into, From, Deref, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UtcOffsetType(UtcOffset);
+
+const OFFSET_FORMAT: &[FormatItem<'static>] =
+    format_description!("[offset_hour sign:mandatory]:[offset_minute]");
+const OFFSET_FORMAT_SECONDS: &[FormatItem<'static>] =
+    format_description!("[offset_hour sign:mandatory]:[offset_minute]:[offset_second]");
+const OFFSET_FORMAT_BASIC: &[FormatItem<'static>] =
+    format_description!("[offset_hour sign:mandatory][offset_minute]");
+const OFFSET_FORMAT_HOURS: &[FormatItem<'static>] =
+    format_description!("[offset_hour sign:mandatory]");
+
+impl FromStr for UtcOffsetType {
+    type Err = time::error::Parse;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s == "Z" || s == "z" {
+            return Ok(Self(UtcOffset::UTC));
+        }
+        UtcOffset::parse(s, OFFSET_FORMAT)
+            .or_else(|e| UtcOffset::parse(s, OFFSET_FORMAT_SECONDS).map_err(|_| e))
+            .or_else(|e| UtcOffset::parse(s, OFFSET_FORMAT_BASIC).map_err(|_| e))
+            .or_else(|e| UtcOffset::parse(s, OFFSET_FORMAT_HOURS).map_err(|_| e))
+            .map(Self)
+    }
+}
+
+impl fmt::Display for UtcOffsetType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let format = if self.0.seconds_past_minute() == 0 {
+            OFFSET_FORMAT
+        } else {
+            OFFSET_FORMAT_SECONDS
+        };
+        match self.0.format(format) {
+            Ok(s) => f.write_str(&s),
+            Err(_) => write!(f, "{}", self.0),
+        }
+    }
+}
+
+string_serde!(UtcOffsetType);
+
+impl Entity for UtcOffsetType {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("utc-offset")
+    }
+
+    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Inline(Schema {
+            description: Cow::Borrowed("Offset from UTC"),
+            schema_type: Some(Type::String),
+            format: Self::type_name(),
+            example: Some(serde_json::value::Value::String("+05:30".into())),
+            ..Schema::default()
+        })
+    }
+}
+
+/// Error returned for a name that is not in the IANA time zone database.
+#[cfg(feature = "time-tz")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownTimeZone(pub String);
+
+#[cfg(feature = "time-tz")]
+impl fmt::Display for UnknownTimeZone {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "unknown time zone {}", self.0)
+    }
+}
+
+#[cfg(feature = "time-tz")]
+impl std::error::Error for UnknownTimeZone {}
+
+/// IANA time zone serialized by name, e.g. `America/New_York`.
+#[cfg(feature = "time-tz")]
+#[derive(Into, From, Deref, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeZoneType(&'static Tz);
+
+#[cfg(feature = "time-tz")]
+impl std::hash::Hash for TimeZoneType {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.name().hash(state);
+    }
+}
+
+#[cfg(feature = "time-tz")]
+impl FromStr for TimeZoneType {
+    type Err = UnknownTimeZone;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        time_tz::timezones::get_by_name(s.trim())
+            .map(Self)
+            .ok_or_else(|| UnknownTimeZone(s.into()))
+    }
+}
+
+#[cfg(feature = "time-tz")]
+impl fmt::Display for TimeZoneType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(self.0.name())
+    }
+}
+
+#[cfg(feature = "time-tz")]
+string_serde!(TimeZoneType);
+
+#[cfg(feature = "time-tz")]
+impl Entity for TimeZoneType {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("time-zone")
+    }
+
+    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Inline(Schema {
+            description: Cow::Borrowed("IANA time zone name"),
+            schema_type: Some(Type::String),
+            format: Self::type_name(),
+            example: Some(serde_json::value::Value::String("America/New_York".into())),
+            ..Schema::default()
+        })
+    }
+}
@@ -0,0 +1,53 @@
+use derive_more::{Deref, Display, From, FromStr, Into};
+use once_cell::sync::Lazy;
+use rweb::openapi::{ComponentDescriptor, ComponentOrInlineSchema, Entity, Schema, Type};
+use serde::{Deserialize, Serialize};
+use std::{borrow::Cow, str::FromStr};
+use uuid::Uuid;
+
+static UUID_EXAMPLE: Lazy<Uuid> =
+    Lazy::new(|| Uuid::from_str("334518f4-1bfd-4f20-9978-bfad0dc033e1").unwrap());
+
+#[derive(
+    Into,
+    From,
+    Serialize,
+    Deserialize,
+    Deref,
+    Clone,
+    Copy,
+    Debug,
+    Hash,
+    PartialEq,
+    Eq,
+    FromStr,
+    Display,
+)]
+pub struct UuidWrapper(Uuid);
+
+impl Entity for UuidWrapper {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("uuid")
+    }
+
+    fn describe(_: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        ComponentOrInlineSchema::Inline(Schema {
+            schema_type: Some(Type::String),
+            format: Self::type_name(),
+            example: Some(serde_json::value::Value::String(UUID_EXAMPLE.to_string())),
+            ..Default::default()
+        })
+    }
+}
+
+impl PartialEq<Uuid> for UuidWrapper {
+    fn eq(&self, other: &Uuid) -> bool {
+        &self.0 == other
+    }
+}
+
+impl PartialEq<UuidWrapper> for Uuid {
+    fn eq(&self, other: &UuidWrapper) -> bool {
+        self == &other.0
+    }
+}
@@ -0,0 +1,654 @@
+use rweb::{
+    filters::BoxedFilter,
+    http::StatusCode,
+    hyper::{body::Bytes, Body, Response},
+    openapi::{
+        ComponentDescriptor, ComponentOrInlineSchema, Entity, MediaType, ResponseEntity, Responses,
+        Schema,
+    },
+    reject::Reject,
+    Filter, FromRequest, Rejection, Reply,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+};
+
+use crate::{
+    schema_util::resolve_schema,
+    schema_validation::{validate, validate_schema, Violation},
+};
+
+pub use rweb_helper_macro::Validate;
+
+/// Rejection listing every way a request body disagrees with its schema.
+///
+/// A body that isn't json at all is rejected with `400 Bad Request`, a json
+/// body that fails validation with `422 Unprocessable Entity`.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, rweb::Schema)]
+pub struct ValidationError {
+    pub error: String,
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationError {
+    #[must_use]
+    pub fn new(violations: Vec<Violation>) -> Self {
+        Self {
+            error: "validation failed".into(),
+            violations,
+        }
+    }
+
+    fn malformed(error: &serde_json::Error) -> Self {
+        Self {
+            error: format!("malformed json body: {}", error),
+            violations: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn status(&self) -> StatusCode {
+        if self.violations.is_empty() {
+            StatusCode::BAD_REQUEST
+        } else {
+            StatusCode::UNPROCESSABLE_ENTITY
+        }
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        for violation in &self.violations {
+            write!(f, "; {}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl Reject for ValidationError {}
+
+impl Reply for ValidationError {
+    fn into_response(self) -> Response<Body> {
+        let status = self.status();
+        let mut res = rweb::reply::json(&self).into_response();
+        *res.status_mut() = status;
+        res
+    }
+}
+
+impl ResponseEntity for ValidationError {
+    fn describe_responses(comp_d: &mut ComponentDescriptor) -> Responses {
+        let schema = Self::describe(comp_d);
+        let response = |description: &'static str| rweb::openapi::Response {
+            description: Cow::Borrowed(description),
+            content: vec![(
+                Cow::Borrowed("application/json"),
+                MediaType {
+                    schema: Some(schema.clone()),
+                    ..MediaType::default()
+                },
+            )]
+            .into_iter()
+            .collect(),
+            ..rweb::openapi::Response::default()
+        };
+        let mut map = Responses::new();
+        map.insert(Cow::Borrowed("400"), response("Malformed json body"));
+        map.insert(
+            Cow::Borrowed("422"),
+            response("Request body failed validation"),
+        );
+        map
+    }
+}
+
+/// Field level validation, usually derived with `#[derive(Validate)]`.
+///
+/// ```ignore
+/// #[derive(Deserialize, Schema, Validate)]
+/// struct NewUser {
+///     #[validate(length(min = 1, max = 64))]
+///     name: String,
+///     #[validate(range(min = 13, max = 150))]
+///     age: u8,
+///     #[validate(email)]
+///     email: String,
+///     #[validate(regex = "^[a-z]+$", custom = not_reserved)]
+///     handle: String,
+/// }
+/// ```
+///
+/// The same constraints are added to the documented schema as `minLength`,
+/// `maxLength`, `minimum`, `maximum`, `pattern` and `format`.
+pub trait Validate {
+    /// # Errors
+    /// Returns every violated constraint, keyed by the json pointer of the
+    /// field
+    fn validate(&self) -> Result<(), ValidationError>;
+
+    /// Constraints keyed by serialized field name.
+    fn constraints() -> Vec<(&'static str, Constraints)> {
+        Vec::new()
+    }
+}
+
+/// Constraints declared on a single field.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Constraints {
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+    pub pattern: Option<&'static str>,
+    pub format: Option<&'static str>,
+}
+
+pub(crate) fn number(value: f64) -> Option<serde_json::Number> {
+    if value.fract() == 0.0 && value.abs() < 9_007_199_254_740_992.0 {
+        Some((value as i64).into())
+    } else {
+        serde_json::Number::from_f64(value)
+    }
+}
+
+impl Constraints {
+    pub fn apply(&self, schema: &mut Schema) {
+        if let Some(min_length) = self.min_length {
+            schema.min_length = Some(min_length as _);
+        }
+        if let Some(max_length) = self.max_length {
+            schema.max_length = Some(max_length as _);
+        }
+        if let Some(minimum) = self.minimum {
+            schema.minimum = number(minimum);
+        }
+        if let Some(maximum) = self.maximum {
+            schema.maximum = number(maximum);
+        }
+        if let Some(pattern) = self.pattern {
+            schema.pattern = Some(pattern.into());
+        }
+        if let Some(format) = self.format {
+            schema.format = Cow::Borrowed(format);
+        }
+    }
+}
+
+/// The schema of `T` with the constraints of its `Validate` impl applied.
+pub fn constrained_schema<T>(comp_d: &mut ComponentDescriptor) -> ComponentOrInlineSchema
+where
+    T: Entity + Validate,
+{
+    let described = T::describe(comp_d);
+    let constraints = T::constraints();
+    if constraints.is_empty() {
+        return described;
+    }
+    let mut schema = match resolve_schema(comp_d, &described) {
+        Some(schema) => schema,
+        None => return described,
+    };
+    for (name, constraints) in constraints {
+        if let Some(property) = schema.properties.get_mut(name) {
+            if let Some(mut resolved) = resolve_schema(comp_d, property) {
+                constraints.apply(&mut resolved);
+                *property = ComponentOrInlineSchema::Inline(resolved);
+            }
+        }
+    }
+    ComponentOrInlineSchema::Inline(schema)
+}
+
+/// A `#[validate(regex = "...")]` pattern, compiled on first use.
+///
+/// `#[derive(Validate)]` declares one static per field, after checking that
+/// the pattern compiles.
+#[doc(hidden)]
+pub struct Pattern {
+    source: &'static str,
+    regex: once_cell::sync::OnceCell<regex::Regex>,
+}
+
+impl Pattern {
+    #[must_use]
+    pub const fn new(source: &'static str) -> Self {
+        Self {
+            source,
+            regex: once_cell::sync::OnceCell::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        self.source
+    }
+
+    /// # Panics
+    /// Panics if the pattern is not a valid regular expression, which
+    /// `#[derive(Validate)]` rules out at compile time
+    #[must_use]
+    pub fn is_match(&self, value: &str) -> bool {
+        self.regex
+            .get_or_init(|| regex::Regex::new(self.source).expect("invalid validation pattern"))
+            .is_match(value)
+    }
+}
+
+pub(crate) fn is_email(value: &str) -> bool {
+    let mut parts = value.splitn(2, '@');
+    let local = parts.next().unwrap_or_default();
+    let domain = parts.next().unwrap_or_default();
+    !local.is_empty()
+        && !domain.contains('@')
+        && !value.chars().any(char::is_whitespace)
+        && domain
+            .split('.')
+            .all(|label| !label.is_empty() && !label.starts_with('-') && !label.ends_with('-'))
+        && domain.contains('.')
+}
+
+pub(crate) fn is_url(value: &str) -> bool {
+    match value.find("://") {
+        Some(i) => {
+            let (scheme, rest) = (&value[..i], &value[i + 3..]);
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+                && !rest.is_empty()
+                && !rest.starts_with('/')
+                && !value.chars().any(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+/// Values with a length, for `#[validate(length(...))]`.
+#[doc(hidden)]
+pub trait ValidateLength {
+    /// `None` skips the check (an absent optional value).
+    fn validated_length(&self) -> Option<usize>;
+}
+
+impl ValidateLength for String {
+    fn validated_length(&self) -> Option<usize> {
+        Some(self.chars().count())
+    }
+}
+
+impl ValidateLength for str {
+    fn validated_length(&self) -> Option<usize> {
+        Some(self.chars().count())
+    }
+}
+
+impl<T> ValidateLength for Vec<T> {
+    fn validated_length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T: ValidateLength> ValidateLength for Option<T> {
+    fn validated_length(&self) -> Option<usize> {
+        self.as_ref().and_then(ValidateLength::validated_length)
+    }
+}
+
+/// Numeric values, for `#[validate(range(...))]`.
+#[doc(hidden)]
+pub trait ValidateRange {
+    fn validated_number(&self) -> Option<f64>;
+}
+
+macro_rules! validate_range {
+    ($($t:ty),*) => {
+        $(
+            impl ValidateRange for $t {
+                fn validated_number(&self) -> Option<f64> {
+                    Some(*self as f64)
+                }
+            }
+        )*
+    };
+}
+
+validate_range!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+impl<T: ValidateRange> ValidateRange for Option<T> {
+    fn validated_number(&self) -> Option<f64> {
+        self.as_ref().and_then(ValidateRange::validated_number)
+    }
+}
+
+/// String values, for `#[validate(regex, email, url)]`.
+#[doc(hidden)]
+pub trait ValidateStr {
+    fn validated_str(&self) -> Option<&str>;
+}
+
+impl ValidateStr for String {
+    fn validated_str(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl ValidateStr for str {
+    fn validated_str(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl<T: ValidateStr> ValidateStr for Option<T> {
+    fn validated_str(&self) -> Option<&str> {
+        self.as_ref().and_then(ValidateStr::validated_str)
+    }
+}
+
+#[doc(hidden)]
+pub fn check_length<V: ValidateLength + ?Sized>(
+    path: &str,
+    value: &V,
+    min: Option<usize>,
+    max: Option<usize>,
+    violations: &mut Vec<Violation>,
+) {
+    if let Some(length) = value.validated_length() {
+        if let Some(min) = min.filter(|min| length < *min) {
+            violations.push(Violation::new(
+                path,
+                format!("length {} is less than {}", length, min),
+            ));
+        }
+        if let Some(max) = max.filter(|max| length > *max) {
+            violations.push(Violation::new(
+                path,
+                format!("length {} is greater than {}", length, max),
+            ));
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn check_range<V: ValidateRange + ?Sized>(
+    path: &str,
+    value: &V,
+    min: Option<f64>,
+    max: Option<f64>,
+    violations: &mut Vec<Violation>,
+) {
+    if let Some(n) = value.validated_number() {
+        if let Some(min) = min.filter(|min| n < *min) {
+            violations.push(Violation::new(
+                path,
+                format!("{} is less than the minimum {}", n, min),
+            ));
+        }
+        if let Some(max) = max.filter(|max| n > *max) {
+            violations.push(Violation::new(
+                path,
+                format!("{} is greater than the maximum {}", n, max),
+            ));
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn check_pattern<V: ValidateStr + ?Sized>(
+    path: &str,
+    value: &V,
+    pattern: &Pattern,
+    violations: &mut Vec<Violation>,
+) {
+    if let Some(s) = value.validated_str() {
+        if !pattern.is_match(s) {
+            violations.push(Violation::new(
+                path,
+                format!("{:?} does not match {}", s, pattern.as_str()),
+            ));
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn check_email<V: ValidateStr + ?Sized>(
+    path: &str,
+    value: &V,
+    violations: &mut Vec<Violation>,
+) {
+    if let Some(s) = value.validated_str() {
+        if !is_email(s) {
+            violations.push(Violation::new(
+                path,
+                format!("{:?} is not a valid email", s),
+            ));
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn check_url<V: ValidateStr + ?Sized>(path: &str, value: &V, violations: &mut Vec<Violation>) {
+    if let Some(s) = value.validated_str() {
+        if !is_url(s) {
+            violations.push(Violation::new(path, format!("{:?} is not a valid uri", s)));
+        }
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(value: serde_json::Value) -> Result<T, ValidationError> {
+    serde_json::from_value(value)
+        .map_err(|e| ValidationError::new(vec![Violation::new("", e.to_string())]))
+}
+
+fn parse_json(body: &[u8]) -> Result<serde_json::Value, ValidationError> {
+    serde_json::from_slice(body).map_err(|e| ValidationError::malformed(&e))
+}
+
+fn parse_validated<T>(body: &[u8]) -> Result<T, ValidationError>
+where
+    T: DeserializeOwned + Entity,
+{
+    let value = parse_json(body)?;
+    let violations = validate::<T>(&value);
+    if !violations.is_empty() {
+        return Err(ValidationError::new(violations));
+    }
+    deserialize(value)
+}
+
+fn parse_validate<T>(body: &[u8]) -> Result<T, ValidationError>
+where
+    T: DeserializeOwned + Entity + Validate,
+{
+    let value = parse_json(body)?;
+    let mut comp_d = ComponentDescriptor::new();
+    let schema = constrained_schema::<T>(&mut comp_d);
+    let violations = validate_schema(&comp_d, &schema, &value);
+    if !violations.is_empty() {
+        return Err(ValidationError::new(violations));
+    }
+    let data: T = deserialize(value)?;
+    data.validate()?;
+    Ok(data)
+}
+
+fn json_body<T>(
+    parse: fn(&[u8]) -> Result<T, ValidationError>,
+) -> impl Filter<Extract = (T,), Error = Rejection> + Clone
+where
+    T: Send + 'static,
+{
+    rweb::filters::body::content_length_limit(JSON_BODY_LIMIT)
+        .and(rweb::filters::body::bytes())
+        .and_then(move |body: Bytes| async move { parse(&body).map_err(rweb::reject::custom) })
+}
+
+/// Largest json body accepted by [`json_body_validated`] and
+/// [`json_body_validate`], in bytes.
+pub const JSON_BODY_LIMIT: u64 = 1024 * 1024;
+
+/// Filter deserializing a json body after validating it against the schema
+/// of `T`, rejecting with a [`ValidationError`] listing all violations.
+///
+/// Bodies over [`JSON_BODY_LIMIT`] are rejected before they are read.
+pub fn json_body_validated<T>() -> impl Filter<Extract = (T,), Error = Rejection> + Clone
+where
+    T: DeserializeOwned + Entity + Send + 'static,
+{
+    json_body(parse_validated::<T>)
+}
+
+/// Like [`json_body_validated`], with the [`Validate`] constraints of `T`
+/// checked as well.
+pub fn json_body_validate<T>() -> impl Filter<Extract = (T,), Error = Rejection> + Clone
+where
+    T: DeserializeOwned + Entity + Validate + Send + 'static,
+{
+    json_body(parse_validate::<T>)
+}
+
+/// Request body extractor backed by [`json_body_validated`], documenting
+/// the schema of `T` as the json request body.
+///
+/// Wrap the response of the handler in [`ValidatedResponse`] to document
+/// the rejection and pair the route with [`recover`] to send it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaValidated<T>(pub T);
+
+impl<T> SchemaValidated<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Entity for SchemaValidated<T>
+where
+    T: Entity,
+{
+    fn type_name() -> Cow<'static, str> {
+        T::type_name()
+    }
+
+    fn describe(comp_d: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        T::describe(comp_d)
+    }
+}
+
+impl<T> FromRequest for SchemaValidated<T>
+where
+    T: DeserializeOwned + Entity + Send + 'static,
+{
+    type Filter = BoxedFilter<(Self,)>;
+
+    fn is_body() -> bool {
+        true
+    }
+
+    fn content_type() -> &'static str {
+        "application/json"
+    }
+
+    fn new() -> Self::Filter {
+        json_body_validated::<T>().map(SchemaValidated).boxed()
+    }
+}
+
+/// Request body extractor backed by [`json_body_validate`].
+///
+/// Wrap the response of the handler in [`ValidatedResponse`] to document
+/// the rejection and pair the route with [`recover`] to send it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Validated<T>(pub T);
+
+impl<T> Validated<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Entity for Validated<T>
+where
+    T: Entity + Validate,
+{
+    fn type_name() -> Cow<'static, str> {
+        T::type_name()
+    }
+
+    fn describe(comp_d: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        constrained_schema::<T>(comp_d)
+    }
+}
+
+impl<T> FromRequest for Validated<T>
+where
+    T: DeserializeOwned + Entity + Validate + Send + 'static,
+{
+    type Filter = BoxedFilter<(Self,)>;
+
+    fn is_body() -> bool {
+        true
+    }
+
+    fn content_type() -> &'static str {
+        "application/json"
+    }
+
+    fn new() -> Self::Filter {
+        json_body_validate::<T>().map(Validated).boxed()
+    }
+}
+
+/// `recover` handler turning a [`ValidationError`] rejection into its 400 or
+/// 422 response, e.g. `route.recover(validation::recover)`.
+///
+/// # Errors
+/// Passes through every other rejection
+pub async fn recover(rejection: Rejection) -> Result<ValidationError, Rejection> {
+    match rejection.find::<ValidationError>() {
+        Some(e) => Ok(e.clone()),
+        None => Err(rejection),
+    }
+}
+
+/// Response of a handler taking a [`SchemaValidated`] or [`Validated`] body.
+///
+/// rweb only documents responses from the return type of a handler, so the
+/// `400` and `422` [`ValidationError`] rejections of the body are added to
+/// the responses of `R` here.
+pub struct ValidatedResponse<R>(pub R);
+
+impl<R> From<R> for ValidatedResponse<R> {
+    fn from(response: R) -> Self {
+        Self(response)
+    }
+}
+
+impl<R: Reply> Reply for ValidatedResponse<R> {
+    fn into_response(self) -> Response<Body> {
+        self.0.into_response()
+    }
+}
+
+impl<R: Entity> Entity for ValidatedResponse<R> {
+    fn type_name() -> Cow<'static, str> {
+        R::type_name()
+    }
+
+    fn describe(comp_d: &mut ComponentDescriptor) -> ComponentOrInlineSchema {
+        R::describe(comp_d)
+    }
+}
+
+impl<R: ResponseEntity> ResponseEntity for ValidatedResponse<R> {
+    fn describe_responses(comp_d: &mut ComponentDescriptor) -> Responses {
+        let mut resp = R::describe_responses(comp_d);
+        for (code, response) in ValidationError::describe_responses(comp_d) {
+            resp.entry(code).or_insert(response);
+        }
+        resp
+    }
+}
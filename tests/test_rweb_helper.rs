@@ -15,7 +15,7 @@ use rweb::{
 
 use rweb_helper::{
     derive_rweb_schema, derive_rweb_test, html_response::HtmlResponse, json_response::JsonResponse,
-    DecimalWrapper, RwebResponse, UuidWrapper,
+    DateType, DecimalWrapper, RwebResponse, RwebSchemaAs, UuidWrapper,
 };
 
 #[test]
@@ -59,17 +59,17 @@ fn basic_example() {
     assert_eq!(expected, observed);
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy)]
+#[derive(Serialize, Deserialize, Clone, Copy, RwebSchemaAs)]
+#[rweb_schema_as(Test1)]
 struct Test0 {
     a: u8,
     b: u8,
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy)]
+#[derive(Serialize, Deserialize, Clone, Copy, RwebSchemaAs)]
+#[rweb_schema_as(Test1)]
 struct Test01(Test0);
 
-derive_rweb_schema!(Test01, Test1);
-
 #[allow(dead_code)]
 #[derive(Schema)]
 struct Test1 {
@@ -134,6 +134,83 @@ fn test_derive_rweb_test() {
     derive_rweb_test!(Test0, Test1);
 }
 
+#[test]
+#[allow(unnameable_test_items)]
+fn test_schema_as() {
+    #[allow(dead_code)]
+    #[derive(Serialize, RwebSchemaAs)]
+    #[serde(rename_all = "camelCase")]
+    #[rweb_schema_as(Test1)]
+    struct Renamed {
+        #[serde(rename = "a")]
+        #[rweb_schema_as(field = "a")]
+        first: u8,
+        b: u8,
+        #[serde(skip)]
+        cache: Option<String>,
+    }
+
+    let mut comp_d = ComponentDescriptor::new();
+    assert_eq!(Renamed::type_name(), Test1::type_name());
+    assert_eq!(Test01::type_name(), Test1::type_name());
+    let _ = Renamed::describe(&mut comp_d);
+    assert_eq!(Renamed::check_schema_as(), Ok(()));
+
+    mod target {
+        #[allow(dead_code)]
+        #[derive(serde::Serialize, rweb::Schema)]
+        #[serde(rename_all = "camelCase")]
+        pub struct Private {
+            pub first_name: String,
+            #[serde(skip)]
+            cache: Option<u32>,
+            pub count: u32,
+        }
+    }
+
+    #[derive(Serialize, Schema)]
+    struct Counted {
+        count: u32,
+    }
+
+    #[derive(Serialize, RwebSchemaAs)]
+    #[serde(bound(serialize = ""))]
+    #[rweb_schema_as(target::Private)]
+    struct Flattened {
+        #[serde(rename = "firstName")]
+        #[rweb_schema_as(field = "first_name")]
+        name: String,
+        #[serde(flatten)]
+        counted: Counted,
+    }
+
+    let _ = Flattened::describe(&mut comp_d);
+    assert_eq!(Flattened::check_schema_as(), Ok(()));
+}
+
+#[test]
+#[allow(unnameable_test_items)]
+fn test_schema_as_renamed_mismatch() {
+    #[derive(Serialize, RwebSchemaAs)]
+    #[rweb_schema_as(Test1)]
+    struct Mismatched {
+        a: u8,
+        #[serde(rename = "c")]
+        b: u8,
+    }
+
+    assert_eq!(
+        Mismatched::check_schema_as(),
+        Err("field c is not a property of the target".to_string())
+    );
+}
+
+#[test]
+fn test_compile_fail() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
+
 #[test]
 fn test_api_spec() {
     fn get_api_path() -> BoxedFilter<(impl Reply,)> {
@@ -146,3 +223,1298 @@ fn test_api_spec() {
     let expected = include_str!("test_schema.json");
     assert_eq!(&spec_json, expected);
 }
+
+#[test]
+fn test_paginated_response() {
+    use rweb_helper::pagination::{Paginated, PaginatedResponse, PaginationQuery};
+
+    let query = PaginationQuery {
+        limit: Some(2),
+        offset: Some(2),
+        cursor: None,
+    };
+    let page = Paginated::from_offset(vec![3u32, 4], 7, &query, "/items");
+    assert_eq!(page.next.as_deref(), Some("/items?limit=2&offset=4"));
+    assert_eq!(page.prev.as_deref(), Some("/items?limit=2&offset=0"));
+
+    let res = PaginatedResponse::<u32, Infallible>::new(page, "/items")
+        .unwrap()
+        .into_response();
+    assert_eq!(res.headers()["x-total-count"], "7");
+    assert_eq!(
+        res.headers()["link"],
+        "</items?limit=2&offset=4>; rel=\"next\", </items?limit=2&offset=0>; rel=\"prev\", \
+         </items?limit=2&offset=0>; rel=\"first\", </items?limit=2&offset=6>; rel=\"last\""
+    );
+
+    let query = PaginationQuery {
+        limit: Some(2),
+        offset: Some(usize::MAX),
+        cursor: None,
+    };
+    let mut page = Paginated::from_offset(Vec::<u32>::new(), 7, &query, "/items");
+    assert_eq!(page.next, None);
+    page.limit = 0;
+    let res = PaginatedResponse::<u32, Infallible>::new(page.clone(), "/items")
+        .unwrap()
+        .into_response();
+    let link = res.headers()["link"].to_str().unwrap();
+    assert!(link.contains("</items?limit=1&offset=0>; rel=\"first\""));
+    assert!(link.contains("</items?limit=1&offset=6>; rel=\"last\""));
+    assert!(PaginatedResponse::<u32, Infallible>::new(page, "/items\n").is_err());
+
+    let mut comp_d = ComponentDescriptor::new();
+    let responses = PaginatedResponse::<u32, Infallible>::describe_responses(&mut comp_d);
+    let responses = serde_json::to_value(&responses).unwrap();
+    assert!(responses["200"]["headers"]["Link"].is_object());
+    assert_eq!(
+        responses["200"]["headers"]["X-Total-Count"]["schema"]["type"],
+        "integer"
+    );
+}
+
+#[test]
+fn test_cursor() {
+    use rweb_helper::cursor::{Cursor, CursorError};
+
+    let key = (UuidWrapper::from(Uuid::new_v4()), 7u32);
+    let cursor = Cursor::encode_signed(&key, b"secret").unwrap();
+    let parsed: Cursor = cursor.to_string().parse().unwrap();
+    assert_eq!(
+        parsed.decode_signed::<(UuidWrapper, u32)>(b"secret"),
+        Ok(key)
+    );
+    assert_eq!(
+        parsed.decode_signed::<(UuidWrapper, u32)>(b"other"),
+        Err(CursorError::InvalidSignature)
+    );
+    assert_eq!(
+        parsed.decode::<(UuidWrapper, u32)>(),
+        Err(CursorError::UnexpectedSignature)
+    );
+    assert!("not a cursor".parse::<Cursor>().is_err());
+
+    let res = CursorError::InvalidEncoding.into_response();
+    assert_eq!(res.status(), rweb::http::StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_cursor_query_rejection() {
+    use rweb_helper::{
+        cursor,
+        pagination::{pagination_query, parse_pagination_query, PaginationQuery},
+    };
+
+    #[get("/items")]
+    async fn list_items(
+        query: PaginationQuery,
+    ) -> Result<JsonResponse<usize, Infallible>, Rejection> {
+        Ok(JsonResponse::new(query.limit()))
+    }
+
+    let route = pagination_query()
+        .map(|query: PaginationQuery| query.limit().to_string())
+        .recover(cursor::recover);
+
+    assert!(parse_pagination_query("limit=0").is_err());
+
+    let res = rweb::test::request()
+        .path("/items?limit=5&cursor=not%20a%20cursor")
+        .reply(&route)
+        .await;
+    assert_eq!(res.status(), 400);
+    let body: serde_json::Value = serde_json::from_slice(res.body()).unwrap();
+    assert_eq!(body["error"], "cursor is not valid url-safe base64");
+
+    let cursor = cursor::Cursor::encode(&7u32).unwrap();
+    let res = rweb::test::request()
+        .path(&format!("/items?limit=5&cursor={}", cursor))
+        .reply(&route)
+        .await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.body().as_ref(), b"5");
+
+    let res = rweb::test::request()
+        .path("/items?limit=3")
+        .reply(&list_items())
+        .await;
+    assert_eq!(res.body().as_ref(), b"3");
+
+    let (spec, _) = openapi::spec().build(|| list_items());
+    let spec = serde_json::to_value(&spec).unwrap();
+    let parameters = spec["paths"]["/items"]["get"]["parameters"]
+        .as_array()
+        .unwrap();
+    let mut names: Vec<_> = parameters
+        .iter()
+        .map(|p| {
+            assert_eq!(p["in"], "query");
+            p["name"].as_str().unwrap()
+        })
+        .collect();
+    names.sort_unstable();
+    assert_eq!(names, ["cursor", "limit", "offset"]);
+}
+
+#[tokio::test]
+async fn test_sparse_fields() {
+    use rweb_helper::fields::Fields;
+
+    #[derive(Serialize, Schema)]
+    struct Inner {
+        a: u8,
+        b: u8,
+    }
+
+    #[derive(Serialize, Schema)]
+    struct Outer {
+        id: UuidWrapper,
+        name: String,
+        inner: Inner,
+    }
+
+    let err = Fields::<Outer>::parse("id,missing").err().unwrap();
+    assert_eq!(err.unknown, vec!["missing".to_string()]);
+    assert!(err.valid.contains(&"inner.a".to_string()));
+    assert_eq!(err.into_response().status(), 400);
+
+    let fields = Fields::<Outer>::parse("name,inner.b").unwrap();
+    let data = Outer {
+        id: Uuid::new_v4().into(),
+        name: "test".into(),
+        inner: Inner { a: 1, b: 2 },
+    };
+    let res = JsonResponse::<_, Infallible>::new(data)
+        .with_fields(fields)
+        .into_response();
+    let body = rweb::hyper::body::to_bytes(res.into_body()).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body, serde_json::json!({"name": "test", "inner": {"b": 2}}));
+}
+
+#[test]
+fn test_envelope() {
+    use rweb_helper::{
+        envelope::{Envelope, EnvelopeKeys, EnvelopeMeta},
+        pagination::{Paginated, PaginationQuery},
+    };
+
+    let page = Paginated::from_offset(vec![1u32, 2], 2, &PaginationQuery::default(), "/items");
+    let envelope = Envelope::from(page);
+    let value = serde_json::to_value(&envelope).unwrap();
+    assert_eq!(value["data"], serde_json::json!([1, 2]));
+    assert_eq!(value["meta"]["pagination"]["total"], 2);
+
+    let mut comp_d = ComponentDescriptor::new();
+    let schema = Envelope::<Vec<u32>>::describe(&mut comp_d);
+    let schema = serde_json::to_value(&schema).unwrap();
+    assert!(schema["properties"]["data"].is_object());
+    assert!(schema["properties"]["meta"].is_object());
+
+    let meta = EnvelopeMeta::default().with_request_id(Uuid::new_v4());
+    let res = JsonResponse::<Envelope<u32>, Infallible>::enveloped(1, meta).into_response();
+    assert_eq!(res.status(), 200);
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct ResultKeys;
+
+    impl EnvelopeKeys for ResultKeys {
+        const DATA: &'static str = "result";
+        const META: &'static str = "metadata";
+        const ERRORS: &'static str = "problems";
+    }
+
+    let keyed = Envelope::<_, ResultKeys>::keyed(3u32);
+    let value = serde_json::to_value(&keyed).unwrap();
+    assert_eq!(value, serde_json::json!({"result": 3, "metadata": {}}));
+    assert_eq!(
+        serde_json::from_value::<Envelope<u32, ResultKeys>>(value).unwrap(),
+        keyed
+    );
+    let schema = Envelope::<u32, ResultKeys>::describe(&mut comp_d);
+    let schema = serde_json::to_value(&schema).unwrap();
+    assert!(schema["properties"]["result"].is_object());
+    assert_eq!(
+        schema["required"],
+        serde_json::json!(["result", "metadata"])
+    );
+}
+
+#[test]
+fn test_json_api_response() {
+    use rweb_helper::json_api::{JsonApiError, JsonApiErrors, JsonApiResource, JsonApiResponse};
+
+    #[derive(Serialize, Schema)]
+    struct Article {
+        id: UuidWrapper,
+        title: String,
+    }
+
+    impl JsonApiResource for Article {
+        fn resource_type() -> &'static str {
+            "articles"
+        }
+        fn resource_id(&self) -> UuidWrapper {
+            self.id
+        }
+    }
+
+    #[get("/article")]
+    async fn article() -> Result<JsonApiResponse<Article, Infallible>, Rejection> {
+        let article = Article {
+            id: Uuid::new_v4().into(),
+            title: "title".into(),
+        };
+        Ok(JsonApiResponse::new(article))
+    }
+
+    let (spec, _) = openapi::spec().build(|| article());
+    let spec = serde_json::to_value(&spec).unwrap();
+    let content = &spec["paths"]["/article"]["get"]["responses"]["200"]["content"];
+    let data = &content["application/vnd.api+json"]["schema"]["properties"]["data"];
+    assert!(data["properties"]["attributes"]["properties"]["title"].is_object());
+    assert!(data["properties"]["attributes"]["properties"]["id"].is_null());
+    let relationship = &data["properties"]["relationships"]["additionalProperties"];
+    assert!(relationship["properties"]["data"]["oneOf"].is_array());
+    assert_eq!(
+        data["properties"]["links"]["additionalProperties"]["type"],
+        "string"
+    );
+
+    #[get("/checked")]
+    async fn checked() -> Result<JsonApiResponse<Article, JsonApiErrors>, Rejection> {
+        Err(rweb::reject::custom(JsonApiErrors::from(
+            JsonApiError::new(rweb::http::StatusCode::NOT_FOUND, "no such article"),
+        )))
+    }
+
+    let (spec, _) = openapi::spec().build(|| checked());
+    let spec = serde_json::to_value(&spec).unwrap();
+    let default = &spec["paths"]["/checked"]["get"]["responses"]["default"];
+    assert!(default["content"]["application/vnd.api+json"]["schema"].is_object());
+
+    let errors = JsonApiErrors::from(JsonApiError::new(
+        rweb::http::StatusCode::NOT_FOUND,
+        "no such article",
+    ));
+    let res = errors.into_response();
+    assert_eq!(res.status(), 404);
+    assert_eq!(res.headers()["content-type"], "application/vnd.api+json");
+
+    fn fail<S: serde::Serializer>(_: &String, _: S) -> Result<S::Ok, S::Error> {
+        Err(serde::ser::Error::custom("unserializable"))
+    }
+
+    #[derive(Serialize, Schema)]
+    struct Broken {
+        id: UuidWrapper,
+        #[serde(serialize_with = "fail")]
+        title: String,
+    }
+
+    impl JsonApiResource for Broken {
+        fn resource_type() -> &'static str {
+            "broken"
+        }
+        fn resource_id(&self) -> UuidWrapper {
+            self.id
+        }
+    }
+
+    let broken = Broken {
+        id: Uuid::new_v4().into(),
+        title: "title".into(),
+    };
+    assert!(JsonApiResponse::<Article, Infallible>::new(Article {
+        id: Uuid::new_v4().into(),
+        title: "title".into(),
+    })
+    .with_included(&broken)
+    .is_err());
+    let res = JsonApiResponse::<Broken, Infallible>::new(broken).into_response();
+    assert_eq!(res.status(), 500);
+}
+
+#[test]
+fn test_with_links() {
+    use rweb::http::Method;
+    use rweb_helper::links::{LinkError, Links, Route, Routes, WithLinks};
+
+    #[derive(Serialize, Schema)]
+    struct Item {
+        id: UuidWrapper,
+    }
+
+    #[get("/items/{id}")]
+    async fn get_item(id: UuidWrapper) -> Result<JsonResponse<Item, Infallible>, Rejection> {
+        Ok(JsonResponse::new(Item { id }))
+    }
+
+    let route = Route::from_handler(get_item).unwrap();
+    assert_eq!(route, Route::new(Method::GET, "/items/{id}"));
+    let id: UuidWrapper = Uuid::new_v4().into();
+    let link = route.link(&[("id", &id)]).unwrap();
+    assert_eq!(link.href, format!("/items/{}", id));
+    assert_eq!(
+        route.link(&[("other", &id)]),
+        Err(LinkError {
+            path: "/items/{id}".into(),
+            missing: vec!["id".into()],
+        })
+    );
+
+    let (spec, _) = openapi::spec().build(|| get_item());
+    let routes = Routes::from_spec(&spec);
+    assert_eq!(routes.get(&Method::GET, "/items/{id}"), Some(&route));
+    assert!(routes.get(&Method::GET, "/missing").is_none());
+
+    let spec: rweb::openapi::Spec = serde_json::from_value(serde_json::json!({
+        "openapi": "3.0.0",
+        "info": {"title": "items", "version": "1"},
+        "paths": {"/items": {
+            "parameters": [{
+                "name": "q",
+                "in": "query",
+                "schema": {"type": "string"},
+            }],
+            "get": {"responses": {}},
+        }},
+    }))
+    .unwrap();
+    let routes = Routes::from_spec(&spec);
+    assert!(routes.get(&Method::GET, "/items").is_some());
+    let parameters = Method::from_bytes(b"PARAMETERS").unwrap();
+    assert!(routes.get(&parameters, "/items").is_none());
+
+    let item = WithLinks::new(Item { id }, Links::new().with("self", link));
+    let value = serde_json::to_value(&item).unwrap();
+    assert_eq!(value["_links"]["self"]["method"], "GET");
+    assert_eq!(value["id"], id.to_string());
+
+    let mut comp_d = ComponentDescriptor::new();
+    let schema = serde_json::to_value(WithLinks::<Item>::describe(&mut comp_d)).unwrap();
+    assert!(schema["properties"]["_links"].is_object());
+    assert!(schema["properties"]["id"].is_object());
+}
+
+#[test]
+fn test_named_status() {
+    use rweb_helper::status_code_trait::{StatusCodeTrait, StatusCodeValue};
+
+    #[derive(RwebResponse)]
+    #[response(description = "accepted", status = "ACCEPTED")]
+    struct AcceptedResponse(JsonResponse<u32, Infallible>);
+
+    #[derive(RwebResponse)]
+    #[response(description = "teapot", status = "IM_A_TEAPOT")]
+    struct TeapotResponse(JsonResponse<u32, Infallible>);
+
+    let res = AcceptedResponse::from(JsonResponse::new(1)).into_response();
+    assert_eq!(res.status(), 202);
+    let res = TeapotResponse::from(JsonResponse::new(1)).into_response();
+    assert_eq!(res.status(), 418);
+    assert_eq!(StatusCodeValue::<206>::status_code(), 206);
+
+    let mut comp_d = ComponentDescriptor::new();
+    let responses = AcceptedResponse::describe_responses(&mut comp_d);
+    assert!(responses.contains_key("202"));
+    assert!(!responses.contains_key("200"));
+}
+
+#[test]
+fn test_response_description_parameter() {
+    use rweb_helper::derive_response_description;
+
+    struct ItemDescription {}
+
+    derive_response_description!(ItemDescription, "A single item");
+
+    #[get("/item")]
+    async fn item() -> Result<JsonResponse<u32, TestError, ItemDescription>, Rejection> {
+        Ok(JsonResponse::new(1))
+    }
+
+    #[get("/page")]
+    async fn page() -> Result<HtmlResponse<&'static str, TestError, ItemDescription>, Rejection> {
+        Ok(HtmlResponse::new("page"))
+    }
+
+    let (spec, _) = openapi::spec().build(|| item().or(page()));
+    let spec = serde_json::to_value(&spec).unwrap();
+    for path in &["/item", "/page"] {
+        let responses = &spec["paths"][path]["get"]["responses"];
+        assert_eq!(responses["200"]["description"], "A single item");
+        assert_eq!(responses["404"]["description"], "Not Found");
+    }
+}
+
+#[test]
+fn test_custom_content_type() {
+    use rweb_helper::derive_content_type;
+
+    #[derive(RwebResponse)]
+    #[response(description = "spreadsheet", content = "application/vnd.ms-excel")]
+    struct ExcelResponse(HtmlResponse<String, Infallible>);
+
+    struct ContentTypeCsv {}
+
+    derive_content_type!(ContentTypeCsv, "text/csv");
+
+    #[derive(RwebResponse)]
+    #[response(description = "csv", content_type = "ContentTypeCsv")]
+    struct CsvResponse(HtmlResponse<String, Infallible>);
+
+    #[derive(RwebResponse)]
+    #[response(description = "json as text", content = "text/plain")]
+    struct TextResponse(JsonResponse<u32, Infallible>);
+
+    let res = ExcelResponse::from(HtmlResponse::new(String::new())).into_response();
+    assert_eq!(res.headers()["content-type"], "application/vnd.ms-excel");
+    let res = CsvResponse::from(HtmlResponse::new(String::new())).into_response();
+    assert_eq!(res.headers()["content-type"], "text/csv; charset=utf-8");
+
+    let mut comp_d = ComponentDescriptor::new();
+    let responses = ExcelResponse::describe_responses(&mut comp_d);
+    let content = &responses["200"].content;
+    assert!(content.contains_key("application/vnd.ms-excel"));
+    assert_eq!(content.len(), 1);
+
+    let responses = TextResponse::describe_responses(&mut comp_d);
+    let content = &responses["200"].content;
+    assert!(content.contains_key("text/plain"));
+    assert!(!content.contains_key("application/json"));
+}
+
+#[test]
+fn test_response_headers() {
+    use rweb::http::{header::HeaderName, HeaderValue};
+
+    #[derive(RwebResponse)]
+    #[response(
+        description = "with headers",
+        header(
+            name = "X-Request-Id",
+            schema = "UuidWrapper",
+            description = "Request identifier"
+        ),
+        header(name = "X-RateLimit-Remaining", schema = "u32")
+    )]
+    struct HeaderResponse(JsonResponse<u32, Infallible>);
+
+    let mut comp_d = ComponentDescriptor::new();
+    let responses = HeaderResponse::describe_responses(&mut comp_d);
+    let responses = serde_json::to_value(&responses).unwrap();
+    let headers = &responses["200"]["headers"];
+    assert_eq!(headers["X-Request-Id"]["description"], "Request identifier");
+    assert_eq!(headers["X-Request-Id"]["schema"]["format"], "uuid");
+    assert_eq!(
+        headers["X-RateLimit-Remaining"]["schema"]["type"],
+        "integer"
+    );
+
+    let res = HeaderResponse::from(
+        JsonResponse::new(1)
+            .with_header(
+                HeaderName::from_static("x-request-id"),
+                HeaderValue::from_str(&Uuid::new_v4().to_string()).unwrap(),
+            )
+            .with_header(
+                HeaderName::from_static("x-ratelimit-remaining"),
+                HeaderValue::from(10u32),
+            ),
+    )
+    .into_response();
+    assert!(res.headers().contains_key("x-request-id"));
+
+    let missing = std::panic::catch_unwind(|| {
+        HeaderResponse::from(JsonResponse::new(1)).into_response();
+    });
+    assert_eq!(missing.is_err(), cfg!(debug_assertions));
+
+    #[derive(RwebResponse)]
+    #[response(status = "CREATED")]
+    struct CreatedResponse(JsonResponse<u32, Infallible>);
+
+    #[derive(RwebResponse)]
+    #[response(header(name = "Location", description = "Created resource"))]
+    struct LocationResponse(CreatedResponse);
+
+    let responses = LocationResponse::describe_responses(&mut comp_d);
+    let responses = serde_json::to_value(&responses).unwrap();
+    assert!(responses["200"].is_null());
+    assert_eq!(
+        responses["201"]["headers"]["Location"]["description"],
+        "Created resource"
+    );
+}
+
+#[test]
+fn test_generic_and_named_response() {
+    #[derive(RwebResponse)]
+    #[response(description = "generic", status = "CREATED")]
+    struct GenericResponse<T>(JsonResponse<T, TestError>)
+    where
+        T: Serialize + Entity + Send;
+
+    #[derive(RwebResponse)]
+    #[response(description = "named")]
+    struct NamedResponse {
+        #[response(inner)]
+        inner: HtmlResponse<&'static str, Infallible>,
+        note: Option<String>,
+    }
+
+    #[derive(RwebResponse)]
+    struct SingleFieldResponse {
+        response: HtmlResponse<String, Infallible>,
+    }
+
+    #[get("/generic")]
+    async fn generic() -> Result<GenericResponse<TestJson>, Rejection> {
+        let test = TestJson {
+            field: "test_field".into(),
+            id: Uuid::new_v4().into(),
+            value: Decimal::from_str_exact("1.234").unwrap().into(),
+        };
+        Ok(JsonResponse::new(test).into())
+    }
+
+    #[derive(Serialize, Schema)]
+    struct TestJson {
+        field: String,
+        id: UuidWrapper,
+        value: DecimalWrapper,
+    }
+
+    let (spec, _) = openapi::spec().build(|| generic());
+    let spec = serde_json::to_value(&spec).unwrap();
+    let responses = &spec["paths"]["/generic"]["get"]["responses"];
+    assert_eq!(responses["201"]["description"], "generic");
+    assert_eq!(responses["404"]["description"], "Not Found");
+
+    let mut comp_d = ComponentDescriptor::new();
+    let responses = NamedResponse::describe_responses(&mut comp_d);
+    assert_eq!(responses["200"].description, "named");
+    let res = NamedResponse::from(HtmlResponse::new("test")).into_response();
+    assert_eq!(res.status(), 200);
+    let res = SingleFieldResponse::from(HtmlResponse::new(String::new())).into_response();
+    assert_eq!(res.status(), 200);
+}
+
+#[derive(Serialize, Schema)]
+struct ExampleJson {
+    name: String,
+    count: u32,
+}
+
+fn example_json() -> ExampleJson {
+    ExampleJson {
+        name: "example".into(),
+        count: 3,
+    }
+}
+
+#[derive(RwebResponse)]
+#[response(description = "from fn", example = example_json)]
+struct FnExampleResponse(JsonResponse<ExampleJson, Infallible>);
+
+#[derive(RwebResponse)]
+#[response(
+    description = "from json",
+    example = r#"{"name": "literal", "count": 1}"#
+)]
+struct JsonExampleResponse(JsonResponse<ExampleJson, Infallible>);
+
+#[test]
+fn test_response_example() {
+    let mut comp_d = ComponentDescriptor::new();
+    let responses = FnExampleResponse::describe_responses(&mut comp_d);
+    let responses = serde_json::to_value(&responses).unwrap();
+    assert_eq!(
+        responses["200"]["content"]["application/json"]["example"],
+        serde_json::json!({"name": "example", "count": 3})
+    );
+
+    let responses = JsonExampleResponse::describe_responses(&mut comp_d);
+    let responses = serde_json::to_value(&responses).unwrap();
+    assert_eq!(
+        responses["200"]["content"]["application/json"]["example"]["name"],
+        "literal"
+    );
+}
+
+#[test]
+#[allow(unnameable_test_items)]
+fn test_response_example_mismatch() {
+    #[derive(RwebResponse)]
+    #[response(example = r#"{"name": "missing count"}"#)]
+    struct BadExampleResponse(JsonResponse<ExampleJson, Infallible>);
+
+    assert!(FnExampleResponse::check_response_example().is_ok());
+    assert!(BadExampleResponse::check_response_example()
+        .unwrap_err()
+        .contains("count"));
+
+    let mut comp_d = ComponentDescriptor::new();
+    let responses = BadExampleResponse::describe_responses(&mut comp_d);
+    let responses = serde_json::to_value(&responses).unwrap();
+    assert!(responses["200"]["content"]["application/json"]["example"].is_null());
+}
+
+#[test]
+fn test_deprecated_response() {
+    use rweb::http::Method;
+    use rweb_helper::response_description_trait::{deprecate_operation, deprecate_operations};
+
+    #[derive(RwebResponse)]
+    #[response(
+        description = "Old endpoint",
+        deprecated,
+        sunset = "2027-01-01",
+        link = "https://example.com/deprecations/old"
+    )]
+    struct OldResponse(JsonResponse<u32, Infallible>);
+
+    let res = OldResponse::from(JsonResponse::new(1)).into_response();
+    assert_eq!(res.headers()["deprecation"], "true");
+    assert_eq!(res.headers()["sunset"], "Fri, 01 Jan 2027 00:00:00 GMT");
+    assert_eq!(
+        res.headers()["link"],
+        "<https://example.com/deprecations/old>; rel=\"deprecation\""
+    );
+
+    let mut comp_d = ComponentDescriptor::new();
+    let responses = OldResponse::describe_responses(&mut comp_d);
+    assert_eq!(
+        responses["200"].description,
+        "Old endpoint\n\nWill be removed on 2027-01-01. See https://example.com/deprecations/old."
+    );
+    assert!(responses["200"].headers.contains_key("Sunset"));
+    let responses = serde_json::to_value(&responses).unwrap();
+    let schema = &responses["200"]["content"]["application/json"]["schema"];
+    assert_eq!(schema["deprecated"], true);
+
+    #[derive(RwebResponse)]
+    #[response(deprecated)]
+    struct BareResponse(JsonResponse<u32, Infallible>);
+
+    let responses = BareResponse::describe_responses(&mut comp_d);
+    assert!(!responses["200"].description.contains("\n\n"));
+    let responses = serde_json::to_value(&responses).unwrap();
+    let schema = &responses["200"]["content"]["application/json"]["schema"];
+    assert_eq!(schema["deprecated"], true);
+
+    #[get("/old")]
+    async fn old_endpoint() -> Result<OldResponse, Rejection> {
+        Ok(JsonResponse::new(1).into())
+    }
+
+    #[get("/current")]
+    async fn current_endpoint() -> Result<JsonResponse<u32, Infallible>, Rejection> {
+        Ok(JsonResponse::new(1))
+    }
+
+    let (mut spec, _) = openapi::spec().build(|| old_endpoint().or(current_endpoint()));
+    deprecate_operations(&mut spec);
+    let value = serde_json::to_value(&spec).unwrap();
+    assert_eq!(value["paths"]["/old"]["get"]["deprecated"], true);
+    assert!(value["paths"]["/current"]["get"]["deprecated"] != true);
+
+    assert!(deprecate_operation(&mut spec, &Method::GET, "/current"));
+    assert!(!deprecate_operation(&mut spec, &Method::POST, "/current"));
+    let value = serde_json::to_value(&spec).unwrap();
+    assert_eq!(value["paths"]["/current"]["get"]["deprecated"], true);
+}
+
+#[derive(Serialize, Schema)]
+struct SchemaSample {
+    id: UuidWrapper,
+    value: DecimalWrapper,
+    tags: Vec<String>,
+    note: Option<String>,
+}
+
+#[derive(Serialize)]
+struct DriftedSample {
+    id: u32,
+    tags: Vec<u32>,
+    extra: bool,
+}
+
+derive_rweb_schema!(DriftedSample, SchemaSample);
+
+#[test]
+fn test_schema_validation() {
+    use rweb_helper::{
+        schema_validation::{assert_matches_schema, check_sample, validate},
+        DateTimeType,
+    };
+
+    assert_matches_schema(&SchemaSample {
+        id: Uuid::new_v4().into(),
+        value: Decimal::new(1234, 3).into(),
+        tags: vec!["a".into()],
+        note: None,
+    });
+
+    let err = check_sample(&DriftedSample {
+        id: 1,
+        tags: vec![2],
+        extra: true,
+    })
+    .unwrap_err();
+    let violations: Vec<String> = err.violations.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        violations,
+        vec![
+            "/: missing required field value",
+            "/extra: not declared in the schema",
+            "/id: expected string, found integer 1",
+            "/tags/0: expected string, found integer 2",
+        ]
+    );
+
+    fn fail<S: serde::Serializer>(_: &String, _: S) -> Result<S::Ok, S::Error> {
+        Err(serde::ser::Error::custom("unserializable"))
+    }
+
+    #[derive(Serialize, Schema)]
+    struct Unserializable {
+        #[serde(serialize_with = "fail")]
+        name: String,
+    }
+
+    let err = check_sample(&Unserializable {
+        name: "name".into(),
+    })
+    .unwrap_err();
+    assert_eq!(err.violations.len(), 1);
+    assert_eq!(
+        err.violations[0].to_string(),
+        "/: failed to serialize: unserializable"
+    );
+
+    #[allow(dead_code)]
+    #[derive(Schema)]
+    struct Dated {
+        day: DateType,
+        at: DateTimeType,
+    }
+
+    for (day, at) in &[
+        ("2023é1-01", "2023-01-01T00:00:00Z"),
+        ("2023-01-01", "2023-01-01T0é0:00:0Z"),
+        ("2023-01-01", "2023-01-01T00:00:00+0é:00"),
+    ] {
+        let violations = validate::<Dated>(&serde_json::json!({"day": day, "at": at}));
+        assert_eq!(violations.len(), 1, "{} {}", day, at);
+    }
+}
+
+#[tokio::test]
+async fn test_json_body_validated() {
+    use rweb::post;
+    use rweb_helper::validation::{
+        self, json_body_validate, json_body_validated, SchemaValidated, Validate,
+        ValidatedResponse, ValidationError, JSON_BODY_LIMIT,
+    };
+
+    #[derive(Deserialize, Schema, Debug)]
+    struct Body {
+        id: UuidWrapper,
+        count: u32,
+        tags: Vec<String>,
+    }
+
+    let filter = json_body_validated::<Body>();
+    let body = rweb::test::request()
+        .body(r#"{"id": "334518f4-1bfd-4f20-9978-bfad0dc033e1", "count": 1, "tags": []}"#)
+        .filter(&filter)
+        .await
+        .unwrap();
+    assert_eq!(body.count, 1);
+
+    let body = rweb::test::request()
+        .body(
+            r#"{"id": "334518f4-1bfd-4f20-9978-bfad0dc033e1", "count": 2, "tags": [], "extra": 1}"#,
+        )
+        .filter(&filter)
+        .await
+        .unwrap();
+    assert_eq!(body.count, 2);
+
+    let rejection = rweb::test::request()
+        .body(r#"{"id": "not-a-uuid", "tags": [1]}"#)
+        .filter(&filter)
+        .await
+        .unwrap_err();
+    let err = rejection.find::<ValidationError>().unwrap();
+    let paths: Vec<&str> = err.violations.iter().map(|v| v.path.as_str()).collect();
+    assert_eq!(paths, vec!["", "/id", "/tags/0"]);
+    assert_eq!(err.clone().into_response().status(), 422);
+
+    let rejection = rweb::test::request()
+        .body("not json")
+        .filter(&filter)
+        .await
+        .unwrap_err();
+    let err = rejection.find::<ValidationError>().unwrap();
+    assert_eq!(err.clone().into_response().status(), 400);
+
+    let rejection = rweb::test::request()
+        .body(vec![b' '; JSON_BODY_LIMIT as usize + 1])
+        .filter(&filter)
+        .await
+        .unwrap_err();
+    assert!(rejection.find::<ValidationError>().is_none());
+
+    let mut comp_d = ComponentDescriptor::new();
+    let responses = ValidationError::describe_responses(&mut comp_d);
+    assert!(responses.contains_key("400"));
+    assert!(responses.contains_key("422"));
+    let responses =
+        ValidatedResponse::<JsonResponse<u32, Infallible>>::describe_responses(&mut comp_d);
+    assert!(responses.contains_key("200"));
+    assert!(responses.contains_key("400"));
+    assert!(responses.contains_key("422"));
+
+    #[post("/items")]
+    async fn create_item(
+        item: SchemaValidated<Body>,
+    ) -> Result<ValidatedResponse<JsonResponse<u32, Infallible>>, Rejection> {
+        Ok(JsonResponse::new(item.into_inner().count).into())
+    }
+
+    let route = create_item().recover(validation::recover);
+    let res = rweb::test::request()
+        .method("POST")
+        .path("/items")
+        .body(r#"{"id": "not-a-uuid", "count": 1, "tags": []}"#)
+        .reply(&route)
+        .await;
+    assert_eq!(res.status(), 422);
+    let body: serde_json::Value = serde_json::from_slice(res.body()).unwrap();
+    assert_eq!(body["violations"][0]["path"], "/id");
+
+    let (spec, _) = openapi::spec().build(|| create_item());
+    let spec = serde_json::to_value(&spec).unwrap();
+    let operation = &spec["paths"]["/items"]["post"];
+    let schema = &operation["requestBody"]["content"]["application/json"]["schema"];
+    assert!(schema["properties"]["count"].is_object());
+    assert!(operation["responses"]["422"].is_object());
+
+    #[derive(Deserialize, Schema, Validate, Debug)]
+    struct Constrained {
+        #[validate(range(min = 1, max = 10))]
+        count: u32,
+    }
+
+    let filter = json_body_validate::<Constrained>();
+    let body = rweb::test::request()
+        .body(r#"{"count": 5}"#)
+        .filter(&filter)
+        .await
+        .unwrap();
+    assert_eq!(body.count, 5);
+
+    let rejection = rweb::test::request()
+        .body(r#"{"count": 11}"#)
+        .filter(&filter)
+        .await
+        .unwrap_err();
+    let err = rejection.find::<ValidationError>().unwrap();
+    let paths: Vec<&str> = err.violations.iter().map(|v| v.path.as_str()).collect();
+    assert_eq!(paths, vec!["/count"]);
+}
+
+#[test]
+fn test_validate() {
+    use rweb_helper::validation::{constrained_schema, Validate};
+
+    fn not_reserved(handle: &str) -> Result<(), String> {
+        if handle == "admin" {
+            Err("handle is reserved".into())
+        } else {
+            Ok(())
+        }
+    }
+
+    #[derive(Deserialize, Schema, Validate)]
+    struct NewUser {
+        #[validate(length(min = 1, max = 8), custom = not_reserved)]
+        handle: String,
+        #[validate(range(min = 13, max = 150))]
+        age: u8,
+        #[validate(email)]
+        email: String,
+        #[validate(regex = "^[A-Z]{2}$")]
+        country: Option<String>,
+    }
+
+    let user = NewUser {
+        handle: "admin".into(),
+        age: 7,
+        email: "not-an-email".into(),
+        country: Some("usa".into()),
+    };
+    let err = user.validate().unwrap_err();
+    let paths: Vec<&str> = err.violations.iter().map(|v| v.path.as_str()).collect();
+    assert_eq!(paths, vec!["/handle", "/age", "/email", "/country"]);
+
+    let user = NewUser {
+        handle: "daniel".into(),
+        age: 40,
+        email: "daniel@example.com".into(),
+        country: None,
+    };
+    assert!(user.validate().is_ok());
+
+    let mut comp_d = ComponentDescriptor::new();
+    let schema = constrained_schema::<NewUser>(&mut comp_d);
+    let schema = serde_json::to_value(&schema).unwrap();
+    assert_eq!(schema["properties"]["handle"]["minLength"], 1);
+    assert_eq!(schema["properties"]["handle"]["maxLength"], 8);
+    assert_eq!(schema["properties"]["age"]["minimum"], 13);
+    assert_eq!(schema["properties"]["age"]["maximum"], 150);
+    assert_eq!(schema["properties"]["email"]["format"], "email");
+    assert_eq!(schema["properties"]["country"]["pattern"], "^[A-Z]{2}$");
+
+    let schema = ComponentOrInlineSchema::Inline(rweb::openapi::Schema {
+        schema_type: Some(rweb::openapi::Type::String),
+        pattern: Some("([a-z".into()),
+        ..rweb::openapi::Schema::default()
+    });
+    let violations = rweb_helper::schema_validation::validate_schema(
+        &comp_d,
+        &schema,
+        &serde_json::json!("abc"),
+    );
+    assert_eq!(violations.len(), 1);
+    assert!(violations[0].message.starts_with("invalid pattern ([a-z"));
+}
+
+#[tokio::test]
+async fn test_query() {
+    use rweb_helper::query::{parse_query, parse_query_with, query, ArrayFormat, QueryError};
+
+    #[derive(Deserialize, Schema, Debug)]
+    struct NameFilter {
+        name: Option<String>,
+        min: Option<u32>,
+    }
+
+    #[derive(Deserialize, Schema, Debug)]
+    struct Params {
+        id: Vec<UuidWrapper>,
+        tag: Vec<String>,
+        price: Option<DecimalWrapper>,
+        since: Option<DateType>,
+        active: Option<bool>,
+        filter: Option<NameFilter>,
+    }
+
+    let a = Uuid::new_v4();
+    let b = Uuid::new_v4();
+    let params: Params = parse_query(&format!(
+        "id={}&id={}&tag=x&tag=y&price=1.50&since=2023-01-01&active=true&filter[name]=a%20b&filter[min]=3",
+        a, b
+    ))
+    .unwrap();
+    assert_eq!(params.id, vec![a, b]);
+    assert_eq!(params.tag, vec!["x".to_string(), "y".to_string()]);
+    assert_eq!(params.price, Some(Decimal::new(150, 2).into()));
+    assert_eq!(params.active, Some(true));
+    let filter = params.filter.unwrap();
+    assert_eq!(filter.name.as_deref(), Some("a b"));
+    assert_eq!(filter.min, Some(3));
+
+    let query = format!("id={}&tag=x,y", a);
+    let params: Params = parse_query(&query).unwrap();
+    assert_eq!(params.tag, vec!["x,y".to_string()]);
+    let params: Params = parse_query_with(&query, ArrayFormat::CommaSeparated).unwrap();
+    assert_eq!(params.tag, vec!["x".to_string(), "y".to_string()]);
+
+    let err = parse_query::<Params>("id=not-a-uuid&tag=x").unwrap_err();
+    assert_eq!(err.parameter, "id");
+    let err = parse_query::<Params>("tag=x&filter[min]=many").unwrap_err();
+    assert_eq!(err.parameter, "filter[min]");
+    assert_eq!(err.reason, "expected an integer, found \"many\"");
+    assert_eq!(err.into_response().status(), 400);
+
+    let rejection = rweb::test::request()
+        .path("/?tag=x")
+        .filter(&query::<Params>())
+        .await
+        .unwrap_err();
+    let err = rejection.find::<QueryError>().unwrap();
+    assert_eq!(err.parameter, "id");
+    assert_eq!(err.reason, "missing required parameter");
+}
+
+#[get("/items/{date}")]
+async fn items_by_date(date: DateType) -> Result<JsonResponse<String, Infallible>, Rejection> {
+    Ok(JsonResponse::new(date.to_string()))
+}
+
+#[tokio::test]
+async fn test_date_path_params() {
+    use rweb_helper::DateTimeType;
+
+    let date: DateType = "2023-01-02".parse().unwrap();
+    assert_eq!(date.to_string(), "2023-01-02");
+    let date: DateType = "20230102".parse().unwrap();
+    assert_eq!(date.to_string(), "2023-01-02");
+
+    let dt: DateTimeType = "2023-01-02T03:04:05Z".parse().unwrap();
+    assert_eq!(dt.to_string(), "2023-01-02T03:04:05Z");
+    let lenient: DateTimeType = "2023-01-02 03:04:05+00:00".parse().unwrap();
+    assert_eq!(lenient, dt);
+    let offset: DateTimeType = "2023-01-02T05:04:05+0200".parse().unwrap();
+    assert_eq!(offset, dt);
+    assert!("2023-01-02".parse::<DateTimeType>().is_err());
+
+    let res = rweb::test::request()
+        .path("/items/2023-01-02")
+        .reply(&items_by_date())
+        .await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.body().as_ref(), b"\"2023-01-02\"");
+
+    let (spec, _) = openapi::spec().build(|| items_by_date());
+    let spec = serde_json::to_value(&spec).unwrap();
+    let parameter = &spec["paths"]["/items/{date}"]["get"]["parameters"][0];
+    assert_eq!(parameter["in"], "path");
+    assert_eq!(parameter["schema"]["format"], "date");
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn test_datetime_formats() {
+    use rweb_helper::{DateTimeType, Rfc2822Type, UnixMillisType, UnixSecondsType};
+
+    let dt: DateTimeType = "2023-01-02T03:04:05Z".parse().unwrap();
+    assert_eq!(
+        serde_json::to_string(&dt).unwrap(),
+        r#""2023-01-02T03:04:05Z""#
+    );
+    let legacy: DateTimeType =
+        serde_json::from_str(r#""2023-01-02 03:04:05.0 +00:00:00""#).unwrap();
+    assert_eq!(legacy, dt);
+
+    let seconds = UnixSecondsType::from(*dt);
+    assert_eq!(serde_json::to_string(&seconds).unwrap(), "1672628645");
+    let millis: UnixMillisType = serde_json::from_str("1672628645000").unwrap();
+    assert_eq!(*millis, *dt);
+    let rfc2822 = Rfc2822Type::from(*dt);
+    assert_eq!(
+        serde_json::to_string(&rfc2822).unwrap(),
+        r#""Mon, 02 Jan 2023 03:04:05 +0000""#
+    );
+
+    let mut comp_d = ComponentDescriptor::new();
+    let schema = serde_json::to_value(UnixMillisType::describe(&mut comp_d)).unwrap();
+    assert_eq!(schema["type"], "integer");
+    assert_eq!(schema["format"], "int64");
+    rweb_helper::schema_validation::assert_matches_schema(&dt);
+    rweb_helper::schema_validation::assert_matches_schema(&seconds);
+}
+
+#[test]
+fn test_time_wrappers() {
+    use rweb_helper::{DurationType, TimeType, UtcOffsetType};
+
+    let time: TimeType = "13:45".parse().unwrap();
+    assert_eq!(serde_json::to_string(&time).unwrap(), r#""13:45:00""#);
+
+    let duration: DurationType = "PT1H30M".parse().unwrap();
+    assert_eq!(duration.whole_seconds(), 5400);
+    assert_eq!(duration.to_string(), "PT1H30M");
+    let seconds: DurationType = serde_json::from_str("5400").unwrap();
+    assert_eq!(seconds, duration);
+    assert!("P1M".parse::<DurationType>().is_err());
+    let long: DurationType = "P10000WT0.000000001S".parse().unwrap();
+    assert_eq!(long.whole_seconds(), 6_048_000_000);
+    assert_eq!(long.subsec_nanoseconds(), 1);
+
+    let offset: UtcOffsetType = "+0530".parse().unwrap();
+    assert_eq!(offset.to_string(), "+05:30");
+    let utc: UtcOffsetType = "Z".parse().unwrap();
+    assert_eq!(utc.to_string(), "+00:00");
+
+    let mut comp_d = ComponentDescriptor::new();
+    let schema = serde_json::to_value(DurationType::describe(&mut comp_d)).unwrap();
+    assert_eq!(schema["format"], "duration");
+    let schema = serde_json::to_value(TimeType::describe(&mut comp_d)).unwrap();
+    assert_eq!(schema["format"], "time");
+}
+
+#[cfg(feature = "time-tz")]
+#[test]
+fn test_time_zone_type() {
+    use rweb_helper::TimeZoneType;
+
+    let tz: TimeZoneType = "America/New_York".parse().unwrap();
+    assert_eq!(serde_json::to_string(&tz).unwrap(), r#""America/New_York""#);
+    assert!("Mars/Olympus_Mons".parse::<TimeZoneType>().is_err());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_chrono_wrappers() {
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use rweb_helper::chrono_types::{DateTimeOffsetType, DateTimeType, DateType};
+
+    let date: DateType = "20230102".parse().unwrap();
+    assert_eq!(*date, NaiveDate::from_ymd_opt(2023, 1, 2).unwrap());
+    assert_eq!(serde_json::to_string(&date).unwrap(), r#""2023-01-02""#);
+
+    let dt: DateTimeType = "2023-01-02 05:04:05+0200".parse().unwrap();
+    assert_eq!(*dt, Utc.with_ymd_and_hms(2023, 1, 2, 3, 4, 5).unwrap());
+    assert_eq!(
+        serde_json::to_string(&dt).unwrap(),
+        r#""2023-01-02T03:04:05Z""#
+    );
+    let offset: DateTimeOffsetType = "2023-01-02T05:04:05+02:00".parse().unwrap();
+    assert_eq!(offset.to_string(), "2023-01-02T05:04:05+02:00");
+    assert_eq!(*offset, *dt);
+
+    let mut comp_d = ComponentDescriptor::new();
+    let schema = |schema: ComponentOrInlineSchema| serde_json::to_value(schema).unwrap();
+    assert_eq!(
+        schema(DateType::describe(&mut comp_d)),
+        schema(rweb_helper::DateType::describe(&mut comp_d))
+    );
+    assert_eq!(
+        schema(DateTimeOffsetType::describe(&mut comp_d)),
+        schema(rweb_helper::DateTimeType::describe(&mut comp_d))
+    );
+    rweb_helper::schema_validation::assert_matches_schema(&dt);
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_postgres_types() {
+    use postgres_types::{FromSql, ToSql, Type};
+    use rweb_helper::DateTimeType;
+
+    let mut buf = bytes::BytesMut::new();
+    let id = UuidWrapper::from(Uuid::new_v4());
+    id.to_sql_checked(&Type::UUID, &mut buf).unwrap();
+    assert_eq!(UuidWrapper::from_sql(&Type::UUID, &buf).unwrap(), id);
+    assert!(id.to_sql_checked(&Type::TEXT, &mut buf).is_err());
+
+    let mut buf = bytes::BytesMut::new();
+    let price = DecimalWrapper::from(Decimal::new(150, 2));
+    price.to_sql_checked(&Type::NUMERIC, &mut buf).unwrap();
+    assert_eq!(
+        DecimalWrapper::from_sql(&Type::NUMERIC, &buf).unwrap(),
+        price
+    );
+
+    let mut buf = bytes::BytesMut::new();
+    let dt: DateTimeType = "2023-01-02T03:04:05Z".parse().unwrap();
+    dt.to_sql_checked(&Type::TIMESTAMPTZ, &mut buf).unwrap();
+    assert_eq!(
+        DateTimeType::from_sql(&Type::TIMESTAMPTZ, &buf).unwrap(),
+        dt
+    );
+    assert!(<DateTimeType as ToSql>::accepts(&Type::TIMESTAMPTZ));
+    assert!(!<DateTimeType as FromSql>::accepts(&Type::TEXT));
+}
+
+#[cfg(feature = "sqlx-postgres")]
+#[test]
+fn test_sqlx_postgres_types() {
+    use sqlx::{postgres::Postgres, Type};
+
+    assert_eq!(
+        <UuidWrapper as Type<Postgres>>::type_info(),
+        <Uuid as Type<Postgres>>::type_info()
+    );
+    assert_eq!(
+        <DecimalWrapper as Type<Postgres>>::type_info(),
+        <Decimal as Type<Postgres>>::type_info()
+    );
+}
+
+#[cfg(feature = "sqlx-sqlite")]
+#[tokio::test]
+async fn test_sqlx_sqlite_types() {
+    use rweb_helper::DateTimeType;
+    use sqlx::{sqlite::Sqlite, Connection, Row, SqliteConnection, Type};
+
+    assert_eq!(
+        <DateTimeType as Type<Sqlite>>::type_info(),
+        <time::OffsetDateTime as Type<Sqlite>>::type_info()
+    );
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    sqlx::query("CREATE TABLE items (id BLOB, price TEXT, amount REAL, count INTEGER)")
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    let id: UuidWrapper = Uuid::new_v4().into();
+    let price: DecimalWrapper = Decimal::new(12_345, 3).into();
+    sqlx::query("INSERT INTO items VALUES (?, ?, 2.25, 42)")
+        .bind(id)
+        .bind(price)
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    let row = sqlx::query("SELECT id, price, amount, count FROM items")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(row.get::<UuidWrapper, _>("id"), id);
+    assert_eq!(row.get::<String, _>("price"), "12.345");
+    assert_eq!(row.get::<DecimalWrapper, _>("price"), price);
+    // sqlite stores decimals as text but reads numeric columns too
+    assert_eq!(
+        row.get::<DecimalWrapper, _>("amount"),
+        Decimal::new(225, 2).into()
+    );
+    assert_eq!(
+        row.get::<DecimalWrapper, _>("count"),
+        Decimal::from(42).into()
+    );
+}
+
+#[test]
+fn test_decimal_limits() {
+    use rweb_helper::{DecimalNumber, ScaledDecimal, ScaledDecimalNumber};
+
+    type Money = ScaledDecimal<12, 2>;
+
+    let price: Money = serde_json::from_str(r#""1.5""#).unwrap();
+    assert_eq!(serde_json::to_string(&price).unwrap(), r#""1.50""#);
+    assert_eq!(*price, Decimal::new(150, 2));
+    let err = serde_json::from_str::<Money>(r#""1.505""#).unwrap_err();
+    assert!(err.to_string().contains("more than 2 decimal places"));
+    assert!("10000000000.00".parse::<Money>().is_err());
+
+    let total: ScaledDecimalNumber<12, 2> = serde_json::from_str("19.9").unwrap();
+    assert_eq!(serde_json::to_string(&total).unwrap(), "19.9");
+    let amount: DecimalNumber = serde_json::from_str("1.25").unwrap();
+    assert_eq!(serde_json::to_string(&amount).unwrap(), "1.25");
+
+    let mut comp_d = ComponentDescriptor::new();
+    let schema = serde_json::to_value(Money::describe(&mut comp_d)).unwrap();
+    assert_eq!(schema["type"], "string");
+    assert_eq!(schema["pattern"], r"^-?[0-9]{1,10}(\.[0-9]{1,2})?$");
+    assert_eq!(schema["maxLength"], 14);
+    assert!(schema.get("multipleOf").is_none());
+    assert!(schema.get("maximum").is_none());
+    rweb_helper::schema_validation::assert_matches_schema(&price);
+    let violations = rweb_helper::schema_validation::validate::<Money>(&serde_json::json!("1.505"));
+    assert_eq!(violations.len(), 1);
+    let schema = serde_json::to_value(ScaledDecimalNumber::<12, 2>::describe(&mut comp_d)).unwrap();
+    assert_eq!(schema["type"], "number");
+    assert_eq!(schema["multipleOf"], 0.01);
+    assert_eq!(schema["maximum"], 9_999_999_999.99);
+    assert!(schema.get("pattern").is_none());
+    rweb_helper::schema_validation::assert_matches_schema(&total);
+    let violations = rweb_helper::schema_validation::validate::<ScaledDecimalNumber<12, 2>>(
+        &serde_json::json!(1.005),
+    );
+    assert_eq!(violations.len(), 1);
+}
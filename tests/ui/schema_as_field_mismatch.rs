@@ -0,0 +1,17 @@
+use rweb_helper::RwebSchemaAs;
+use serde::Serialize;
+
+#[derive(Serialize, rweb::Schema)]
+struct Target {
+    a: u8,
+    b: u8,
+}
+
+#[derive(Serialize, RwebSchemaAs)]
+#[rweb_schema_as(Target)]
+struct Source {
+    a: u8,
+    b: String,
+}
+
+fn main() {}
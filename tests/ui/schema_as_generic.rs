@@ -0,0 +1,15 @@
+use rweb_helper::RwebSchemaAs;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Target {
+    a: u8,
+}
+
+#[derive(Serialize, RwebSchemaAs)]
+#[rweb_schema_as(Target)]
+struct Source<T> {
+    a: T,
+}
+
+fn main() {}
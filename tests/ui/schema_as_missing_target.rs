@@ -0,0 +1,9 @@
+use rweb_helper::RwebSchemaAs;
+use serde::Serialize;
+
+#[derive(Serialize, RwebSchemaAs)]
+struct Source {
+    a: u8,
+}
+
+fn main() {}
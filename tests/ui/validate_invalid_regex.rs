@@ -0,0 +1,9 @@
+use rweb_helper::validation::Validate;
+
+#[derive(Validate)]
+struct Source {
+    #[validate(regex = "([a-z")]
+    name: String,
+}
+
+fn main() {}